@@ -23,8 +23,6 @@ use specs::{
 };
 use specs_derive::*;
 use std::{
-    cmp::max,
-    cmp::min,
     io::{stdout, Write},
     thread::sleep,
     time::Duration,
@@ -79,25 +77,25 @@ fn try_move_player(dx: i32, dy: i32, ecs: &mut World) {
     let mut players = ecs.write_storage::<Player>();
 
     for (_, pos) in (&mut players, &mut positions).join() {
-        pos.x = min(COLS - 2, max(0, pos.x + dx));
-        pos.y = min(ROWS - 2, max(0, pos.y + dy));
+        pos.x = (pos.x + dx).clamp(0, COLS - 2);
+        pos.y = (pos.y + dy).clamp(0, ROWS - 2);
     }
 }
 
 fn player_input(gs: &mut State) {
-    match gs.event {
-        Some(Event::Key(KeyEvent {
-            modifiers: KeyModifiers::NONE,
-            code,
-        })) => match code {
+    if let Some(Event::Key(KeyEvent {
+        modifiers: KeyModifiers::NONE,
+        code,
+    })) = gs.event
+    {
+        match code {
             KeyCode::Left => try_move_player(-1, 0, &mut gs.ecs),
             KeyCode::Right => try_move_player(1, 0, &mut gs.ecs),
             KeyCode::Up => try_move_player(0, -1, &mut gs.ecs),
             KeyCode::Down => try_move_player(0, 1, &mut gs.ecs),
             KeyCode::Esc => gs.should_exit = true,
             _ => {}
-        },
-        _ => {}
+        }
     }
 }
 
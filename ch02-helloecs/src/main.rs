@@ -5,6 +5,7 @@ use specs::{prelude::*, Builder, World, WorldExt};
 use specs_derive::*;
 
 #[derive(Component)]
+#[allow(dead_code)]
 struct LeftMover {
     pub min_col: i32,
     pub max_col: i32,
@@ -41,8 +42,11 @@ impl<'a> System<'a> for LeftWalker {
 #[derive(Default)]
 struct Ch02Game {}
 impl Game for Ch02Game {
-    fn init(&self, _gs: &GameState, ecs: &mut World) -> Result<()> {
+    fn register(&self, ecs: &mut World) {
         ecs.register::<LeftMover>();
+    }
+
+    fn init(&self, _gs: &GameState, ecs: &mut World) -> Result<()> {
         for i in 0..10 {
             ecs.create_entity()
                 .with(Position { x: i * 7, y: 10 })
@@ -57,10 +61,8 @@ impl Game for Ch02Game {
         Ok(())
     }
 
-    fn update(&mut self, _: &GameState, ecs: &World) -> Result<()> {
-        let mut lw = LeftWalker {};
-        lw.run_now(ecs);
-        Ok(())
+    fn register_systems(&self, dispatcher: &mut DispatcherBuilder<'static, 'static>) {
+        dispatcher.add(LeftWalker {}, "left_walker", &[]);
     }
 }
 
@@ -1,40 +1,33 @@
 use crossterm::Result;
 
 use rand::Rng;
-use rc_game::{Game, GameState, Offset, RogueCrossGame, TileType};
-
-fn xy_idx(x: usize, y: usize, cols: usize) -> usize {
-    (y * cols) + x
-}
+use rc_game::{Game, GameRng, GameState, Map, Offset, RogueCrossGame, TileType};
 
 #[derive(Default)]
 struct Ch03Game {}
 
 impl Game for Ch03Game {}
 
-fn create_map(gs: &GameState, player_position: &Offset) -> Vec<TileType> {
-    let mut map = vec![TileType::Empty; (gs.rows * gs.cols) as usize];
-    let GameState { cols, rows, .. } = *gs;
-    let cols = cols as usize;
-    let rows = rows as usize;
+fn create_map(gs: &GameState, player_position: &mut Offset, rng: &mut GameRng) -> Map {
+    let mut map = Map::new(gs.cols, gs.rows);
+    let cols = gs.cols as i32;
+    let rows = gs.rows as i32;
     // Walls
     for x in 0..cols {
-        map[xy_idx(x, 0, cols)] = TileType::Wall;
-        map[xy_idx(x, rows - 1, cols)] = TileType::Wall;
+        map.set_tile(x, 0, TileType::Wall);
+        map.set_tile(x, rows - 1, TileType::Wall);
     }
     for y in 0..rows {
-        map[xy_idx(0, y, cols)] = TileType::Wall;
-        map[xy_idx(cols - 1, y, cols)] = TileType::Wall;
+        map.set_tile(0, y, TileType::Wall);
+        map.set_tile(cols - 1, y, TileType::Wall);
     }
 
-    let mut rng = rand::thread_rng();
     for _ in 0..400 {
         let x = rng.gen_range(1, cols - 1);
         let y = rng.gen_range(1, rows - 1);
 
-        if x != player_position.x as usize || y != player_position.y as usize {
-            let idx = xy_idx(x, y, cols);
-            map[idx] = TileType::Wall;
+        if x != player_position.x || y != player_position.y {
+            map.set_tile(x, y, TileType::Wall);
         }
     }
     map
@@ -45,3 +38,24 @@ fn main() -> Result<()> {
     game.build_map(create_map);
     game.start()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rc_game::{assert_snapshot, MemoryBackend, TestRunner};
+
+    #[test]
+    fn create_map_with_seed() {
+        let mut game: RogueCrossGame<Ch03Game, MemoryBackend> = Default::default();
+        game.set_seed(1);
+        game.build_map(create_map);
+        let mut runner = TestRunner::new(game).unwrap();
+        runner.step().unwrap();
+
+        let path = format!(
+            "{}/tests/snapshots/create_map_seed_1.snap",
+            env!("CARGO_MANIFEST_DIR")
+        );
+        assert_snapshot(path, &runner.snapshot(true));
+    }
+}
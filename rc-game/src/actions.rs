@@ -0,0 +1,144 @@
+use specs::Entity;
+use std::fmt::Display;
+
+use crate::Offset;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Direction {
+    North,
+    South,
+    East,
+    West,
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::South,
+        Direction::East,
+        Direction::West,
+        Direction::NorthEast,
+        Direction::NorthWest,
+        Direction::SouthEast,
+        Direction::SouthWest,
+    ];
+
+    pub fn delta(&self) -> Offset {
+        match self {
+            Direction::North => Offset::new(0, -1),
+            Direction::South => Offset::new(0, 1),
+            Direction::East => Offset::new(1, 0),
+            Direction::West => Offset::new(-1, 0),
+            Direction::NorthEast => Offset::new(1, -1),
+            Direction::NorthWest => Offset::new(-1, -1),
+            Direction::SouthEast => Offset::new(1, 1),
+            Direction::SouthWest => Offset::new(-1, 1),
+        }
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        let Offset { x, y } = self.delta();
+        x != 0 && y != 0
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Direction::North => "north",
+            Direction::South => "south",
+            Direction::East => "east",
+            Direction::West => "west",
+            Direction::NorthEast => "northeast",
+            Direction::NorthWest => "northwest",
+            Direction::SouthEast => "southeast",
+            Direction::SouthWest => "southwest",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "north" | "up" => Some(Direction::North),
+            "south" | "down" => Some(Direction::South),
+            "east" | "right" => Some(Direction::East),
+            "west" | "left" => Some(Direction::West),
+            "northeast" | "north_east" | "up_right" => Some(Direction::NorthEast),
+            "northwest" | "north_west" | "up_left" => Some(Direction::NorthWest),
+            "southeast" | "south_east" | "down_right" => Some(Direction::SouthEast),
+            "southwest" | "south_west" | "down_left" => Some(Direction::SouthWest),
+            _ => None,
+        }
+    }
+}
+
+/// What the player wants to do, derived from input via the `KeyMap`
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Action {
+    Move(Direction),
+    Wait,
+    PickUp,
+    Quit,
+    /// Show older messages of the `GameLog`
+    ScrollLogUp,
+    /// Show newer messages of the `GameLog`
+    ScrollLogDown,
+    /// Defined and handled by the game via `Game::handle_action`
+    Custom(u32),
+}
+
+impl Action {
+    /// Parses the names used in key map config files, i.e. `move_north`, `wait` or `custom:1`
+    pub fn from_name(name: &str) -> Option<Self> {
+        if let Some(dir) = name.strip_prefix("move_") {
+            return Direction::from_name(dir).map(Action::Move);
+        }
+        if let Some(id) = name.strip_prefix("custom:") {
+            return id.parse().ok().map(Action::Custom);
+        }
+        match name {
+            "wait" => Some(Action::Wait),
+            "pickup" | "pick_up" => Some(Action::PickUp),
+            "quit" => Some(Action::Quit),
+            "scroll_log_up" => Some(Action::ScrollLogUp),
+            "scroll_log_down" => Some(Action::ScrollLogDown),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::Move(dir) => write!(f, "move_{}", dir.name()),
+            Action::Wait => f.write_str("wait"),
+            Action::PickUp => f.write_str("pickup"),
+            Action::Quit => f.write_str("quit"),
+            Action::ScrollLogUp => f.write_str("scroll_log_up"),
+            Action::ScrollLogDown => f.write_str("scroll_log_down"),
+            Action::Custom(id) => write!(f, "custom:{}", id),
+        }
+    }
+}
+
+/// How the game handled an action passed to `Game::handle_action`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ActionOutcome {
+    /// Fall back to the default handling, i.e. moving the player or quitting
+    Unhandled,
+    /// The game handled the action without it costing the player their turn
+    Handled,
+    /// The game handled the action and it ends the player's turn
+    TookTurn,
+}
+
+/// Result of trying to move an entity
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MoveOutcome {
+    Moved,
+    /// The terrain blocks the move
+    Blocked,
+    /// The target tile is occupied by this entity with a `Collider`
+    Bumped(Entity),
+}
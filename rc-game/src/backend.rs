@@ -0,0 +1,259 @@
+use crossterm::{
+    cursor, event, event::Event, execute, queue, style::Print, style::ResetColor,
+    style::SetBackgroundColor, style::SetForegroundColor, terminal, Result,
+};
+use terminal::{disable_raw_mode, enable_raw_mode, ClearType};
+
+use std::{
+    collections::VecDeque,
+    io::{stdout, Stdout, Write},
+    time::Duration,
+};
+
+use crate::{Renderable, GAME_COLS, GAME_ROWS, HUD_ROWS, LOG_ROWS};
+
+/// Everything the game needs from the terminal it is running in.
+/// Swapping the backend allows running a game without a TTY.
+pub trait Backend {
+    /// Size of the terminal as (cols, rows)
+    fn size(&self) -> Result<(u16, u16)>;
+
+    /// Enter raw mode, set the title, clear the screen and hide the cursor
+    fn enter(&mut self, title: &str) -> Result<()>;
+
+    /// Restore the terminal to the state it was in before `enter`
+    fn leave(&mut self) -> Result<()>;
+
+    fn clear(&mut self) -> Result<()>;
+
+    /// Draw a single cell at absolute terminal coordinates
+    fn draw_cell(&mut self, x: u16, y: u16, renderable: &Renderable) -> Result<()>;
+
+    fn flush(&mut self) -> Result<()>;
+
+    /// Wait up to `timeout` for an event and return it if one arrived
+    fn poll_event(&mut self, timeout: Duration) -> Result<Option<Event>>;
+
+    /// Restore the terminal without access to the backend instance, i.e. when panicking
+    fn restore() -> Result<()>
+    where
+        Self: Sized,
+    {
+        Ok(())
+    }
+}
+
+//
+// Crossterm
+//
+pub struct CrosstermBackend {
+    stdout: Stdout,
+}
+
+impl Default for CrosstermBackend {
+    fn default() -> Self {
+        Self { stdout: stdout() }
+    }
+}
+
+impl Backend for CrosstermBackend {
+    fn size(&self) -> Result<(u16, u16)> {
+        terminal::size()
+    }
+
+    fn enter(&mut self, title: &str) -> Result<()> {
+        enable_raw_mode()?;
+        execute!(
+            self.stdout,
+            terminal::EnterAlternateScreen,
+            terminal::SetTitle(title),
+            terminal::Clear(ClearType::All),
+            cursor::Hide,
+        )
+    }
+
+    fn leave(&mut self) -> Result<()> {
+        execute!(
+            self.stdout,
+            ResetColor,
+            terminal::Clear(ClearType::All),
+            cursor::MoveTo(0, 0),
+            cursor::Show,
+            terminal::LeaveAlternateScreen,
+        )?;
+        disable_raw_mode()
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        queue!(self.stdout, ResetColor, terminal::Clear(ClearType::All))
+    }
+
+    fn draw_cell(&mut self, x: u16, y: u16, renderable: &Renderable) -> Result<()> {
+        match renderable.bg {
+            None => queue!(self.stdout, ResetColor),
+            Some(color) => queue!(self.stdout, SetBackgroundColor(color)),
+        }?;
+        queue!(
+            self.stdout,
+            cursor::MoveTo(x, y),
+            SetForegroundColor(renderable.fg),
+            Print(renderable.glyph)
+        )
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.stdout.flush()?;
+        Ok(())
+    }
+
+    fn poll_event(&mut self, timeout: Duration) -> Result<Option<Event>> {
+        if event::poll(timeout)? {
+            Ok(Some(event::read()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn restore() -> Result<()> {
+        execute!(
+            stdout(),
+            ResetColor,
+            cursor::Show,
+            terminal::LeaveAlternateScreen
+        )?;
+        disable_raw_mode()
+    }
+}
+
+//
+// In Memory
+//
+
+/// Backend that keeps all drawn cells in memory and replays queued events.
+/// Polling never blocks, it returns `None` once the queued events are exhausted.
+pub struct MemoryBackend {
+    cols: u16,
+    rows: u16,
+    cells: Vec<Renderable>,
+    events: VecDeque<Event>,
+    title: String,
+    raw_mode: bool,
+    flushes: usize,
+}
+
+impl MemoryBackend {
+    pub fn new(cols: u16, rows: u16) -> Self {
+        Self {
+            cols,
+            rows,
+            cells: vec![Renderable::default(); cols as usize * rows as usize],
+            events: VecDeque::new(),
+            title: String::new(),
+            raw_mode: false,
+            flushes: 0,
+        }
+    }
+
+    /// Changes the size of the terminal, clearing it.
+    /// Push an `Event::Resize` to have the game react to it.
+    pub fn set_size(&mut self, cols: u16, rows: u16) {
+        self.cols = cols;
+        self.rows = rows;
+        self.cells = vec![Renderable::default(); cols as usize * rows as usize];
+    }
+
+    pub fn push_event(&mut self, event: Event) {
+        self.events.push_back(event);
+    }
+
+    pub fn push_events<I>(&mut self, events: I)
+    where
+        I: IntoIterator<Item = Event>,
+    {
+        self.events.extend(events);
+    }
+
+    pub fn pending_events(&self) -> usize {
+        self.events.len()
+    }
+
+    /// The cell at absolute terminal coordinates or `None` if outside the terminal
+    pub fn cell(&self, x: u16, y: u16) -> Option<&Renderable> {
+        if x >= self.cols || y >= self.rows {
+            return None;
+        }
+        self.cells.get(y as usize * self.cols as usize + x as usize)
+    }
+
+    pub fn cells(&self) -> &[Renderable] {
+        &self.cells
+    }
+
+    /// Glyphs of the row at `y` as a string
+    pub fn row_text(&self, y: u16) -> String {
+        (0..self.cols)
+            .filter_map(|x| self.cell(x, y))
+            .map(|cell| cell.glyph)
+            .collect()
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn is_raw_mode(&self) -> bool {
+        self.raw_mode
+    }
+
+    pub fn flushes(&self) -> usize {
+        self.flushes
+    }
+}
+
+impl Default for MemoryBackend {
+    /// Sized to fit the game, status bar and message log including the terminal frame
+    fn default() -> Self {
+        Self::new(GAME_COLS + 2, GAME_ROWS + HUD_ROWS + LOG_ROWS + 3)
+    }
+}
+
+impl Backend for MemoryBackend {
+    fn size(&self) -> Result<(u16, u16)> {
+        Ok((self.cols, self.rows))
+    }
+
+    fn enter(&mut self, title: &str) -> Result<()> {
+        self.title = title.to_string();
+        self.raw_mode = true;
+        self.clear()
+    }
+
+    fn leave(&mut self) -> Result<()> {
+        self.raw_mode = false;
+        Ok(())
+    }
+
+    fn clear(&mut self) -> Result<()> {
+        for cell in self.cells.iter_mut() {
+            *cell = Renderable::default();
+        }
+        Ok(())
+    }
+
+    fn draw_cell(&mut self, x: u16, y: u16, renderable: &Renderable) -> Result<()> {
+        if x < self.cols && y < self.rows {
+            let idx = y as usize * self.cols as usize + x as usize;
+            self.cells[idx] = renderable.clone();
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.flushes += 1;
+        Ok(())
+    }
+
+    fn poll_event(&mut self, _timeout: Duration) -> Result<Option<Event>> {
+        Ok(self.events.pop_front())
+    }
+}
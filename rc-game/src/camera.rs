@@ -0,0 +1,68 @@
+use crate::{Map, Offset, Position};
+
+/// Decides which part of the map is visible on screen, available as a resource in the `World`
+#[derive(Clone, Debug)]
+pub struct Camera {
+    /// World position shown at the top left of the screen
+    pub origin: Offset,
+    /// How far the followed position may move away from the screen center before the camera
+    /// scrolls along
+    pub dead_zone: Offset,
+    /// Stop scrolling at the edges of the map instead of showing what lies beyond them
+    pub clamp_to_map: bool,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self {
+            origin: Offset::default(),
+            dead_zone: Offset::new(8, 4),
+            clamp_to_map: true,
+        }
+    }
+}
+
+impl Camera {
+    /// Scrolls the camera so that `target` stays within the dead zone around the screen center
+    pub fn follow(&mut self, target: &Position, map: &Map, cols: u16, rows: u16) {
+        self.origin.x = follow_axis(self.origin.x, target.x, self.dead_zone.x, cols);
+        self.origin.y = follow_axis(self.origin.y, target.y, self.dead_zone.y, rows);
+        if self.clamp_to_map {
+            self.origin.x = clamp_axis(self.origin.x, map.width, cols);
+            self.origin.y = clamp_axis(self.origin.y, map.height, rows);
+        }
+    }
+
+    /// Places `target` at the screen center, ignoring the dead zone
+    pub fn center_on(&mut self, target: &Position, map: &Map, cols: u16, rows: u16) {
+        let dead_zone = self.dead_zone;
+        self.dead_zone = Offset::default();
+        self.follow(target, map, cols, rows);
+        self.dead_zone = dead_zone;
+    }
+
+    pub fn world_to_screen(&self, x: i32, y: i32) -> (i32, i32) {
+        (x - self.origin.x, y - self.origin.y)
+    }
+
+    pub fn screen_to_world(&self, x: i32, y: i32) -> (i32, i32) {
+        self.origin.apply(x, y)
+    }
+}
+
+fn follow_axis(origin: i32, target: i32, dead_zone: i32, size: u16) -> i32 {
+    let delta = target - (origin + size as i32 / 2);
+    if delta > dead_zone {
+        origin + delta - dead_zone
+    } else if delta < -dead_zone {
+        origin + delta + dead_zone
+    } else {
+        origin
+    }
+}
+
+fn clamp_axis(origin: i32, map_size: u16, size: u16) -> i32 {
+    // Maps that fit on the screen are never scrolled
+    let max = std::cmp::max(0, map_size as i32 - size as i32);
+    origin.clamp(0, max)
+}
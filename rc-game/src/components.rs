@@ -51,5 +51,101 @@ impl Default for Renderable {
     }
 }
 
+/// Layer an entity's `Renderable` is drawn in, higher layers are drawn on top of lower ones.
+/// Entities without one are drawn with the `Actors`.
+#[derive(Component, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
+pub enum RenderLayer {
+    Terrain,
+    Items,
+    #[default]
+    Actors,
+    Effects,
+    Ui,
+}
+
 #[derive(Component, Default)]
 pub struct Collider {}
+
+/// Hostile entity that chases the player once its `Viewshed` sees them
+#[derive(Component, Debug, Default)]
+pub struct Monster {}
+
+#[derive(Component, Debug, Clone)]
+pub struct Name {
+    pub name: String,
+}
+
+impl Name {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+}
+
+/// What an entity can see, recomputed by the `VisibilitySystem` whenever it is `dirty`.
+/// Mark it dirty after the entity moved or the terrain around it changed.
+#[derive(Component)]
+pub struct Viewshed {
+    pub visible_tiles: Vec<Offset>,
+    pub range: i32,
+    pub dirty: bool,
+}
+
+impl Viewshed {
+    pub fn new(range: i32) -> Self {
+        Self {
+            visible_tiles: vec![],
+            range,
+            dirty: true,
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone)]
+pub struct CombatStats {
+    pub max_hp: i32,
+    pub hp: i32,
+    pub defense: i32,
+    pub power: i32,
+}
+
+impl CombatStats {
+    /// Stats at full health
+    pub fn new(max_hp: i32, defense: i32, power: i32) -> Self {
+        Self {
+            max_hp,
+            hp: max_hp,
+            defense,
+            power,
+        }
+    }
+}
+
+/// Intent to attack the target, resolved and removed by the `MeleeCombatSystem`
+#[derive(Component, Debug, Clone)]
+pub struct WantsToMelee {
+    pub target: Entity,
+}
+
+/// Damage taken this turn, applied and removed by the `DamageSystem`
+#[derive(Component, Debug)]
+pub struct SufferDamage {
+    pub amount: Vec<i32>,
+}
+
+impl SufferDamage {
+    /// Adds to the damage the victim already suffers this turn
+    pub fn new_damage(store: &mut WriteStorage<SufferDamage>, victim: Entity, amount: i32) {
+        if let Some(suffering) = store.get_mut(victim) {
+            suffering.amount.push(amount);
+        } else {
+            let damage = SufferDamage {
+                amount: vec![amount],
+            };
+            store
+                .insert(victim, damage)
+                .expect("Unable to insert damage");
+        }
+    }
+}
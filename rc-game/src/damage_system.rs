@@ -0,0 +1,20 @@
+use specs::prelude::*;
+
+use crate::{CombatStats, SufferDamage};
+
+/// Applies the damage suffered this turn to the hit points
+pub struct DamageSystem {}
+
+impl<'a> System<'a> for DamageSystem {
+    type SystemData = (
+        WriteStorage<'a, CombatStats>,
+        WriteStorage<'a, SufferDamage>,
+    );
+
+    fn run(&mut self, (mut stats, mut damage): Self::SystemData) {
+        for (stats, damage) in (&mut stats, &damage).join() {
+            stats.hp -= damage.amount.iter().sum::<i32>();
+        }
+        damage.clear();
+    }
+}
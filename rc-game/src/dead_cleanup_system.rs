@@ -0,0 +1,37 @@
+use crossterm::style::Color;
+use specs::prelude::*;
+
+use crate::{melee_combat_system::name_of, CombatStats, GameLog, Name, Player, RunState};
+
+/// Deletes entities without hit points left, a dead player ends the game instead
+pub struct DeadCleanupSystem {}
+
+impl<'a> System<'a> for DeadCleanupSystem {
+    type SystemData = (
+        Entities<'a>,
+        ReadStorage<'a, CombatStats>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Name>,
+        WriteExpect<'a, RunState>,
+        WriteExpect<'a, GameLog>,
+    );
+
+    fn run(&mut self, (entities, stats, players, names, mut run_state, mut log): Self::SystemData) {
+        for (entity, stats, player) in (&entities, &stats, players.maybe()).join() {
+            if stats.hp > 0 {
+                continue;
+            }
+            if player.is_some() {
+                if *run_state != RunState::GameOver {
+                    log.push_colored("You die.", Color::Red);
+                }
+                *run_state = RunState::GameOver;
+            } else {
+                log.push(format!("{} dies.", name_of(entity, &names, &players)));
+                entities
+                    .delete(entity)
+                    .expect("Unable to delete dead entity");
+            }
+        }
+    }
+}
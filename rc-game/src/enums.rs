@@ -1,7 +1,6 @@
-#[derive(PartialEq, Copy, Clone)]
+#[derive(PartialEq, Copy, Clone, Debug)]
 pub enum TileType {
     Empty,
     Wall,
     Floor,
 }
-
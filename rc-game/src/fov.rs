@@ -0,0 +1,147 @@
+use crate::{Map, Offset};
+
+/// Tiles visible from `origin` within `range` using symmetric shadowcasting.
+/// Walls block sight but are visible themselves, tiles outside the map are never included.
+/// See https://www.albertford.com/shadowcasting/
+pub fn field_of_view(origin: Offset, range: i32, map: &Map) -> Vec<Offset> {
+    let mut fov = Fov {
+        origin,
+        range,
+        map,
+        seen: vec![false; map.tiles.len()],
+        visible: vec![],
+    };
+    if !map.in_bounds(origin.x, origin.y) {
+        return fov.visible;
+    }
+    fov.reveal(origin);
+    for quadrant in &QUADRANTS {
+        let row = Row {
+            depth: 1,
+            start_slope: -1.0,
+            end_slope: 1.0,
+        };
+        fov.scan(quadrant, row);
+    }
+    fov.visible
+}
+
+enum Quadrant {
+    North,
+    East,
+    South,
+    West,
+}
+
+const QUADRANTS: [Quadrant; 4] = [
+    Quadrant::North,
+    Quadrant::East,
+    Quadrant::South,
+    Quadrant::West,
+];
+
+impl Quadrant {
+    fn transform(&self, origin: Offset, depth: i32, col: i32) -> Offset {
+        match self {
+            Quadrant::North => Offset::new(origin.x + col, origin.y - depth),
+            Quadrant::South => Offset::new(origin.x + col, origin.y + depth),
+            Quadrant::East => Offset::new(origin.x + depth, origin.y + col),
+            Quadrant::West => Offset::new(origin.x - depth, origin.y + col),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Row {
+    depth: i32,
+    start_slope: f64,
+    end_slope: f64,
+}
+
+impl Row {
+    fn cols(&self) -> std::ops::RangeInclusive<i32> {
+        let min = round_ties_up(self.depth as f64 * self.start_slope);
+        let max = round_ties_down(self.depth as f64 * self.end_slope);
+        min..=max
+    }
+
+    fn next(&self) -> Row {
+        Row {
+            depth: self.depth + 1,
+            ..*self
+        }
+    }
+
+    fn is_symmetric(&self, col: i32) -> bool {
+        let col = col as f64;
+        let depth = self.depth as f64;
+        col >= depth * self.start_slope && col <= depth * self.end_slope
+    }
+}
+
+struct Fov<'a> {
+    origin: Offset,
+    range: i32,
+    map: &'a Map,
+    seen: Vec<bool>,
+    visible: Vec<Offset>,
+}
+
+impl<'a> Fov<'a> {
+    fn scan(&mut self, quadrant: &Quadrant, mut row: Row) {
+        if row.depth > self.range {
+            return;
+        }
+        let mut prev_wall: Option<bool> = None;
+        for col in row.cols() {
+            let tile = quadrant.transform(self.origin, row.depth, col);
+            let wall = self.is_wall(tile);
+            if (wall || row.is_symmetric(col)) && self.in_range(row.depth, col) {
+                self.reveal(tile);
+            }
+            if prev_wall == Some(true) && !wall {
+                row.start_slope = slope(row.depth, col);
+            }
+            if prev_wall == Some(false) && wall {
+                let mut next = row.next();
+                next.end_slope = slope(row.depth, col);
+                self.scan(quadrant, next);
+            }
+            prev_wall = Some(wall);
+        }
+        if prev_wall == Some(false) {
+            self.scan(quadrant, row.next());
+        }
+    }
+
+    fn in_range(&self, depth: i32, col: i32) -> bool {
+        depth * depth + col * col <= self.range * self.range
+    }
+
+    fn is_wall(&self, tile: Offset) -> bool {
+        self.map.is_opaque(tile.x, tile.y)
+    }
+
+    fn reveal(&mut self, tile: Offset) {
+        if !self.map.in_bounds(tile.x, tile.y) {
+            return;
+        }
+        let idx = self.map.xy_idx(tile.x, tile.y);
+        if !self.seen[idx] {
+            self.seen[idx] = true;
+            self.visible.push(tile);
+        }
+    }
+}
+
+fn slope(depth: i32, col: i32) -> f64 {
+    (2 * col - 1) as f64 / (2 * depth) as f64
+}
+
+fn round_ties_up(n: f64) -> i32 {
+    (n + 0.5).floor() as i32
+}
+
+fn round_ties_down(n: f64) -> i32 {
+    (n - 0.5).ceil() as i32
+}
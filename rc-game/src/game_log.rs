@@ -0,0 +1,102 @@
+use crossterm::style::Color;
+
+use crate::{Renderable, Renderer};
+
+/// Rows of the message panel beneath the play area by default
+pub const LOG_ROWS: u16 = 5;
+
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    /// Turn during which the message was logged
+    pub turn: u32,
+    pub text: String,
+    pub color: Color,
+}
+
+/// Messages shown in the panel beneath the play area, available as a resource in the `World`.
+/// The turn advances each time the player takes an action that ends their turn.
+#[derive(Default)]
+pub struct GameLog {
+    entries: Vec<LogEntry>,
+    turn: u32,
+    /// Number of the newest entries scrolled out of view
+    scroll: usize,
+}
+
+impl GameLog {
+    pub fn push<S: Into<String>>(&mut self, text: S) {
+        self.push_colored(text, Color::White);
+    }
+
+    pub fn push_colored<S: Into<String>>(&mut self, text: S, color: Color) {
+        self.entries.push(LogEntry {
+            turn: self.turn,
+            text: text.into(),
+            color,
+        });
+        // Keep showing the same entries while scrolled back
+        if self.scroll > 0 {
+            self.scroll += 1;
+        }
+    }
+
+    pub fn entries(&self) -> &[LogEntry] {
+        &self.entries
+    }
+
+    pub fn turn(&self) -> u32 {
+        self.turn
+    }
+
+    pub(crate) fn next_turn(&mut self) {
+        self.turn += 1;
+    }
+
+    /// Shows older entries, stops once the oldest one is at the top
+    pub fn scroll_up(&mut self, rows: usize, visible_rows: usize) {
+        let max_scroll = self.entries.len().saturating_sub(visible_rows);
+        self.scroll = usize::min(self.scroll + rows, max_scroll);
+    }
+
+    pub fn scroll_down(&mut self, rows: usize) {
+        self.scroll = self.scroll.saturating_sub(rows);
+    }
+
+    pub fn scroll_to_end(&mut self) {
+        self.scroll = 0;
+    }
+
+    /// Number of newer entries currently out of view
+    pub fn scroll(&self) -> usize {
+        self.scroll
+    }
+
+    /// The entries that fit into the rows at the current scroll position, oldest first
+    pub fn visible(&self, rows: usize) -> &[LogEntry] {
+        let end = self.entries.len() - self.scroll;
+        let start = end.saturating_sub(rows);
+        &self.entries[start..end]
+    }
+}
+
+/// Renders the separator at row `y` followed by the newest entries in the rows beneath it
+pub(crate) fn render_log(renderer: &mut Renderer, log: &GameLog, y: i32, rows: u16) {
+    let cols = renderer.cols() as i32;
+    let separator = Renderable {
+        glyph: '═',
+        ..Default::default()
+    };
+    renderer.hline(0, y, cols, &separator);
+    if log.scroll() > 0 {
+        let more = format!(" {} more ", log.scroll());
+        let x = cols - more.len() as i32 - 1;
+        renderer.print(x, y, &more, Color::White, None);
+    }
+
+    for (row, entry) in log.visible(rows as usize).iter().enumerate() {
+        let y = y + 1 + row as i32;
+        let stamp = format!("{:>4} ", entry.turn);
+        renderer.print(0, y, &stamp, Color::DarkGrey, None);
+        renderer.print(stamp.len() as i32, y, &entry.text, entry.color, None);
+    }
+}
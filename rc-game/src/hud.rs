@@ -0,0 +1,98 @@
+use crossterm::style::Color;
+
+use crate::{CombatStats, Renderable, Renderer};
+
+/// Rows of the status bar beneath the play area
+pub const HUD_ROWS: u16 = 1;
+
+const GAUGE_WIDTH: i32 = 20;
+
+#[derive(Clone, Debug)]
+pub struct Gauge {
+    pub label: String,
+    pub value: i32,
+    pub max: i32,
+    pub color: Color,
+}
+
+/// Gauges the game shows in the status bar in addition to the player's hit points,
+/// available as a resource in the `World`
+#[derive(Default)]
+pub struct Hud {
+    gauges: Vec<Gauge>,
+}
+
+impl Hud {
+    /// Adds the gauge or updates the one with the same label
+    pub fn set_gauge(&mut self, label: &str, value: i32, max: i32, color: Color) {
+        let gauge = Gauge {
+            label: label.to_string(),
+            value,
+            max,
+            color,
+        };
+        match self.gauges.iter_mut().find(|g| g.label == label) {
+            Some(existing) => *existing = gauge,
+            None => self.gauges.push(gauge),
+        }
+    }
+
+    pub fn remove_gauge(&mut self, label: &str) {
+        self.gauges.retain(|g| g.label != label);
+    }
+
+    pub fn gauges(&self) -> &[Gauge] {
+        &self.gauges
+    }
+}
+
+/// Renders the gauges on the left and dungeon depth plus turn on the right of row `y`
+pub(crate) fn render_hud(
+    renderer: &mut Renderer,
+    y: i32,
+    hud: &Hud,
+    player_stats: Option<&CombatStats>,
+    depth: i32,
+    turn: u32,
+) {
+    let hp = player_stats.map(|stats| Gauge {
+        label: "HP".to_string(),
+        value: stats.hp,
+        max: stats.max_hp,
+        color: Color::Red,
+    });
+    let mut x = 0;
+    for gauge in hp.iter().chain(hud.gauges.iter()) {
+        x = render_gauge(renderer, x, y, gauge) + 2;
+    }
+
+    let status = format!("Depth: {}  Turn: {}", depth, turn);
+    let status_x = renderer.cols() as i32 - status.chars().count() as i32;
+    renderer.print(status_x, y, &status, Color::White, None);
+}
+
+/// Renders `label: value/max` followed by the bar, returns the column after it
+fn render_gauge(renderer: &mut Renderer, x: i32, y: i32, gauge: &Gauge) -> i32 {
+    let text = format!("{}: {}/{} ", gauge.label, gauge.value, gauge.max);
+    renderer.print(x, y, &text, Color::White, None);
+    let x = x + text.chars().count() as i32;
+
+    let filled = if gauge.max > 0 {
+        (gauge.value.clamp(0, gauge.max) * GAUGE_WIDTH) / gauge.max
+    } else {
+        0
+    };
+    let bar = Renderable {
+        glyph: '█',
+        fg: gauge.color,
+        bg: None,
+    };
+    let empty = Renderable {
+        glyph: '░',
+        fg: Color::DarkGrey,
+        bg: None,
+    };
+    renderer.hline(x, y, filled, &bar);
+    renderer.hline(x + filled, y, GAUGE_WIDTH - filled, &empty);
+    x + GAUGE_WIDTH
+}
@@ -0,0 +1,278 @@
+use crossterm::{
+    event::{KeyCode, KeyEvent, KeyModifiers},
+    ErrorKind, Result,
+};
+
+use std::{collections::HashMap, fs, io, path::Path};
+
+use crate::{Action, Direction};
+
+/// Maps key presses, including their modifiers, to actions, available as a resource in the `World`.
+///
+/// Config files contain one `key = action` binding per line, `#` starts a comment, i.e.
+///
+/// ```text
+/// preset = vi
+/// ctrl+q = quit
+/// h = move_west
+/// x = none  # removes the binding
+/// ```
+pub struct KeyMap {
+    bindings: HashMap<KeyEvent, Action>,
+}
+
+/// Bundled sets of movement keys
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KeyPreset {
+    /// Arrow keys plus Home, PgUp, End and PgDn for the diagonals
+    Arrows,
+    /// WASD, four directions only
+    Wasd,
+    /// `hjkl` plus `yubn` for the diagonals
+    Vi,
+    /// Number keys laid out like a numpad with `5` to wait
+    Numpad,
+}
+
+impl KeyPreset {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "arrows" => Some(KeyPreset::Arrows),
+            "wasd" => Some(KeyPreset::Wasd),
+            "vi" => Some(KeyPreset::Vi),
+            "numpad" => Some(KeyPreset::Numpad),
+            _ => None,
+        }
+    }
+
+    fn bindings(&self) -> Vec<(KeyCode, Action)> {
+        use Direction::*;
+        let moves: Vec<(KeyCode, Direction)> = match self {
+            KeyPreset::Arrows => vec![
+                (KeyCode::Up, North),
+                (KeyCode::Down, South),
+                (KeyCode::Right, East),
+                (KeyCode::Left, West),
+                (KeyCode::Home, NorthWest),
+                (KeyCode::PageUp, NorthEast),
+                (KeyCode::End, SouthWest),
+                (KeyCode::PageDown, SouthEast),
+            ],
+            KeyPreset::Wasd => vec![
+                (KeyCode::Char('w'), North),
+                (KeyCode::Char('s'), South),
+                (KeyCode::Char('d'), East),
+                (KeyCode::Char('a'), West),
+            ],
+            KeyPreset::Vi => vec![
+                (KeyCode::Char('k'), North),
+                (KeyCode::Char('j'), South),
+                (KeyCode::Char('l'), East),
+                (KeyCode::Char('h'), West),
+                (KeyCode::Char('y'), NorthWest),
+                (KeyCode::Char('u'), NorthEast),
+                (KeyCode::Char('b'), SouthWest),
+                (KeyCode::Char('n'), SouthEast),
+            ],
+            KeyPreset::Numpad => vec![
+                (KeyCode::Char('8'), North),
+                (KeyCode::Char('2'), South),
+                (KeyCode::Char('6'), East),
+                (KeyCode::Char('4'), West),
+                (KeyCode::Char('7'), NorthWest),
+                (KeyCode::Char('9'), NorthEast),
+                (KeyCode::Char('1'), SouthWest),
+                (KeyCode::Char('3'), SouthEast),
+            ],
+        };
+        let mut bindings: Vec<(KeyCode, Action)> = moves
+            .into_iter()
+            .map(|(code, dir)| (code, Action::Move(dir)))
+            .collect();
+        if *self == KeyPreset::Numpad {
+            bindings.push((KeyCode::Char('5'), Action::Wait));
+        }
+        bindings
+    }
+}
+
+impl Default for KeyMap {
+    /// Arrows and WASD to move, `.` to wait, `g` to pick up, Shift+PgUp/PgDn or `[`/`]` to scroll
+    /// the message log and Esc to quit
+    fn default() -> Self {
+        let mut keymap = Self::empty();
+        keymap.add_preset(KeyPreset::Arrows);
+        keymap.add_preset(KeyPreset::Wasd);
+        keymap.bind(KeyCode::Char('.').into(), Action::Wait);
+        keymap.bind(KeyCode::Char('g').into(), Action::PickUp);
+        keymap.bind(
+            KeyEvent::new(KeyCode::PageUp, KeyModifiers::SHIFT),
+            Action::ScrollLogUp,
+        );
+        keymap.bind(
+            KeyEvent::new(KeyCode::PageDown, KeyModifiers::SHIFT),
+            Action::ScrollLogDown,
+        );
+        keymap.bind(KeyCode::Char('[').into(), Action::ScrollLogUp);
+        keymap.bind(KeyCode::Char(']').into(), Action::ScrollLogDown);
+        keymap.bind(KeyCode::Esc.into(), Action::Quit);
+        keymap
+    }
+}
+
+impl KeyMap {
+    pub fn empty() -> Self {
+        Self {
+            bindings: HashMap::new(),
+        }
+    }
+
+    /// Only the bindings of the presets plus Esc to quit
+    pub fn with_presets(presets: &[KeyPreset]) -> Self {
+        let mut keymap = Self::empty();
+        for preset in presets {
+            keymap.add_preset(*preset);
+        }
+        keymap.bind(KeyCode::Esc.into(), Action::Quit);
+        keymap
+    }
+
+    /// Adds the bindings of the preset, replacing existing bindings of the same keys
+    pub fn add_preset(&mut self, preset: KeyPreset) {
+        for (code, action) in preset.bindings() {
+            self.bind(code.into(), action);
+        }
+    }
+
+    /// Default bindings with the ones from the config file at `path` applied on top
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let config = fs::read_to_string(path)?;
+        let mut keymap = Self::default();
+        keymap.apply_config(&config)?;
+        Ok(keymap)
+    }
+
+    /// Applies the bindings of the config, replacing existing bindings of the same keys
+    pub fn apply_config(&mut self, config: &str) -> Result<()> {
+        for (idx, line) in config.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = |msg: String| invalid_config(idx + 1, msg);
+            let mut parts = line.splitn(2, '=');
+            let key = parts.next().unwrap_or("").trim();
+            let action = parts
+                .next()
+                .ok_or_else(|| invalid(format!("expected 'key = action' in '{}'", line)))?
+                .trim();
+
+            if key == "preset" {
+                let preset = KeyPreset::from_name(action)
+                    .ok_or_else(|| invalid(format!("unknown preset '{}'", action)))?;
+                self.add_preset(preset);
+                continue;
+            }
+            let key = parse_key(key).ok_or_else(|| invalid(format!("unknown key '{}'", key)))?;
+            if action == "none" {
+                self.unbind(&key);
+                continue;
+            }
+            let action = Action::from_name(action)
+                .ok_or_else(|| invalid(format!("unknown action '{}'", action)))?;
+            self.bind(key, action);
+        }
+        Ok(())
+    }
+
+    pub fn bind(&mut self, key: KeyEvent, action: Action) {
+        self.bindings.insert(normalize(key), action);
+    }
+
+    pub fn unbind(&mut self, key: &KeyEvent) -> Option<Action> {
+        self.bindings.remove(&normalize(*key))
+    }
+
+    pub fn action_for(&self, key: &KeyEvent) -> Option<Action> {
+        self.bindings.get(&normalize(*key)).copied()
+    }
+
+    /// All keys bound to the action
+    pub fn keys_for(&self, action: Action) -> Vec<KeyEvent> {
+        self.bindings
+            .iter()
+            .filter(|(_, a)| **a == action)
+            .map(|(key, _)| *key)
+            .collect()
+    }
+}
+
+/// Shift is already reflected in the character, i.e. `A` instead of `a`
+fn normalize(key: KeyEvent) -> KeyEvent {
+    match key.code {
+        KeyCode::Char(_) => KeyEvent::new(key.code, key.modifiers - KeyModifiers::SHIFT),
+        _ => key,
+    }
+}
+
+fn invalid_config(line: usize, msg: String) -> ErrorKind {
+    let msg = format!("Invalid key map config on line {}: {}", line, msg);
+    ErrorKind::IoError(io::Error::new(io::ErrorKind::InvalidData, msg))
+}
+
+/// Parses keys like `a`, `ctrl+a`, `shift+left`, `pagedown` or `f5`
+fn parse_key(key: &str) -> Option<KeyEvent> {
+    let mut parts: Vec<&str> = key.split('+').map(str::trim).collect();
+    // Allow binding the `+` key itself
+    if key.ends_with('+') && parts.len() > 1 {
+        parts.truncate(parts.len() - 2);
+        parts.push("+");
+    }
+    let (name, modifier_names) = parts.split_last()?;
+
+    let mut modifiers = KeyModifiers::NONE;
+    for modifier in modifier_names {
+        modifiers |= match modifier.to_lowercase().as_str() {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return None,
+        };
+    }
+
+    let mut chars = name.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+            KeyCode::Char(c.to_ascii_uppercase())
+        }
+        (Some(c), None) => KeyCode::Char(c),
+        _ => parse_key_name(&name.to_lowercase())?,
+    };
+    Some(normalize(KeyEvent::new(code, modifiers)))
+}
+
+fn parse_key_name(name: &str) -> Option<KeyCode> {
+    let code = match name {
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "esc" | "escape" => KeyCode::Esc,
+        "enter" | "return" => KeyCode::Enter,
+        "space" => KeyCode::Char(' '),
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        _ => {
+            let n = name.strip_prefix('f')?.parse().ok()?;
+            KeyCode::F(n)
+        }
+    };
+    Some(code)
+}
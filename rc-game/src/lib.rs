@@ -1,72 +1,194 @@
+mod actions;
+mod backend;
+mod camera;
 mod components;
+mod damage_system;
+mod dead_cleanup_system;
 mod enums;
+mod fov;
+mod game_log;
+mod hud;
+mod keymap;
+mod map;
+mod map_builders;
+mod map_indexing_system;
+mod melee_combat_system;
+mod monster_ai_system;
 mod offset;
+mod pathfinding;
 mod rc_terminal;
+mod rect;
 mod renderables;
 mod renderer;
+mod rng;
+mod rules;
+mod run_state;
+mod snapshot;
+mod spatial_index;
+mod test_runner;
+mod visibility_system;
+pub use crate::rc_terminal::TerminalGuard;
 use crate::rc_terminal::*;
+pub use actions::{Action, ActionOutcome, Direction, MoveOutcome};
+pub use backend::{Backend, CrosstermBackend, MemoryBackend};
+pub use camera::Camera;
 pub use components::*;
+pub use damage_system::DamageSystem;
+pub use dead_cleanup_system::DeadCleanupSystem;
 pub use enums::*;
+pub use fov::field_of_view;
+use game_log::render_log;
+pub use game_log::{GameLog, LogEntry, LOG_ROWS};
+use hud::render_hud;
+pub use hud::{Gauge, Hud, HUD_ROWS};
+pub use keymap::{KeyMap, KeyPreset};
+pub use map::Map;
+pub use map_builders::{rooms_and_corridors, RoomsAndCorridors};
+pub use map_indexing_system::MapIndexingSystem;
+pub use melee_combat_system::MeleeCombatSystem;
+pub use monster_ai_system::MonsterAiSystem;
 pub use offset::Offset;
-use renderables::{renderable_floor, renderable_wall};
+pub use pathfinding::{a_star, a_star_with, Connectivity, DijkstraMap, PathOptions};
+pub use rect::Rect;
+use renderables::{renderable_tile, renderable_tile_remembered};
+pub use renderer::{BoxStyle, Renderer};
+pub use rng::{GameRng, SEED_ARG, SEED_ENV};
+pub use rules::MovementRules;
+pub use run_state::RunState;
+pub use snapshot::{assert_snapshot, Snapshot, UPDATE_SNAPSHOTS_ENV};
+pub use spatial_index::SpatialIndex;
+pub use test_runner::TestRunner;
+pub use visibility_system::VisibilitySystem;
+
+use crossterm::{event::Event, style::Color, ErrorKind, Result};
 
-use crossterm::{
-    cursor, event::poll, event::read, event::Event, event::KeyCode, event::KeyEvent,
-    event::KeyModifiers, execute, style::Color, terminal, Result,
-};
-
-use renderer::Renderer;
 use specs::prelude::*;
-use terminal::{disable_raw_mode, enable_raw_mode, ClearType};
 
-use std::{io::stdout, io::Stdout, io::Write, thread::sleep, time::Duration, time::SystemTime};
+use std::{thread::sleep, time::Duration, time::SystemTime};
 
-const FRAMES_PER_SEC: u64 = 60;
-const MS_PER_FRAME: u64 = 1_000 / FRAMES_PER_SEC;
+pub const FRAMES_PER_SEC: u64 = 60;
+pub const MS_PER_FRAME: u64 = 1_000 / FRAMES_PER_SEC;
 
 pub const GAME_COLS: u16 = 80;
 pub const GAME_ROWS: u16 = 25;
 
-pub fn create_blank_map(gs: &GameState) -> Vec<TileType> {
-    vec![TileType::Empty; (gs.rows * gs.cols) as usize]
+pub fn create_blank_map(gs: &GameState) -> Map {
+    Map::new(gs.cols, gs.rows)
 }
 
 #[allow(unused)]
 pub trait Game: 'static + Default {
+    /// Registers the game's components and resources.
+    /// Runs first, before the map is built and any entity is created.
+    fn register(&self, ecs: &mut World) {}
+    /// Creates entities derived from the map, i.e. monsters or items placed inside its rooms.
+    /// Runs when the game starts, before the player is created.
+    fn spawn_map_entities(&self, gs: &GameState, ecs: &mut World) -> Result<()> {
+        Ok(())
+    }
+    /// Adds the game's components to the player entity when it is created
+    fn build_player<'a>(&self, player: EntityBuilder<'a>) -> EntityBuilder<'a> {
+        player
+    }
+    /// Runs once the terminal is set up, after the map entities and the player were created
     fn init(&self, gs: &GameState, ecs: &mut World) -> Result<()> {
         Ok(())
     }
+    /// Adds the game's systems with their names and dependencies, including thread local ones.
+    /// They run every frame after `update`, followed by `World::maintain`, whether or not a turn
+    /// happened. Use `register_turn_systems` for systems that should only run once per turn.
+    fn register_systems(&self, dispatcher: &mut DispatcherBuilder<'static, 'static>) {}
+    /// Adds systems that run once per turn during the `MonsterTurn`, i.e. monster AI.
+    /// They run after the `MonsterAiSystem` and before `monster_turn`.
+    fn register_turn_systems(&self, dispatcher: &mut DispatcherBuilder<'static, 'static>) {}
+    /// Runs every frame, independent of the `RunState`
     fn update(&mut self, gs: &GameState, ecs: &World) -> Result<()> {
         Ok(())
     }
+    /// Receives each action the player triggers via the `KeyMap` before it is handled by default
+    fn handle_action(
+        &mut self,
+        action: Action,
+        gs: &GameState,
+        ecs: &World,
+    ) -> Result<ActionOutcome> {
+        Ok(ActionOutcome::Unhandled)
+    }
+    /// Called when the player tried to move onto a tile occupied by an entity with a `Collider`.
+    /// Return `ActionOutcome::TookTurn` if the bump, i.e. an attack, ends the player's turn.
+    /// When `Unhandled` the player attacks the target if it is a `Monster` with `CombatStats`.
+    fn bump(
+        &mut self,
+        player: Entity,
+        target: Entity,
+        gs: &GameState,
+        ecs: &World,
+    ) -> Result<ActionOutcome> {
+        Ok(ActionOutcome::Unhandled)
+    }
+    /// Runs once after each player action
+    fn player_turn(&mut self, gs: &GameState, ecs: &World) -> Result<()> {
+        Ok(())
+    }
+    /// Runs once after each player turn, after the `MonsterAiSystem` moved the monsters
+    fn monster_turn(&mut self, gs: &GameState, ecs: &World) -> Result<()> {
+        Ok(())
+    }
+    /// Draws the game's own UI, i.e. menus or tooltips, on top of the map, status bar and log.
+    /// Runs every frame before the buffer is flushed, the renderer covers the whole game area.
+    fn render(&self, gs: &GameState, ecs: &World, renderer: &mut Renderer) {}
 }
 
 pub struct GameState {
     pub cols: u16,
     pub rows: u16,
     event: Option<Event>,
+    action: Option<Action>,
+}
+
+impl GameState {
+    /// The event received this frame if any
+    pub fn event(&self) -> Option<&Event> {
+        self.event.as_ref()
+    }
+
+    /// The action the event received this frame is bound to if any
+    pub fn action(&self) -> Option<Action> {
+        self.action
+    }
 }
 
-pub struct RogueCrossGame<TGame>
+pub struct RogueCrossGame<TGame, TBackend = CrosstermBackend>
 where
     TGame: Game,
+    TBackend: Backend,
 {
     ecs: World,
-    map: Vec<TileType>,
     game: TGame,
     game_state: GameState,
     millis_per_frame: u64,
     renderer: Option<Renderer>,
+    dispatcher: Option<Dispatcher<'static, 'static>>,
+    turn_dispatcher: Option<Dispatcher<'static, 'static>>,
     should_exit: bool,
-    stdout: Stdout,
+    backend: TBackend,
     title: String,
     player_start_position: Offset,
+    player_viewshed: Option<i32>,
+    log_rows: u16,
+    show_hud: bool,
     built_map: bool,
     started: bool,
+    /// The terminal cannot fit the game, nothing is rendered until it is resized
+    too_small: bool,
+    /// Seed from the command line or environment that couldn't be parsed
+    seed_error: Option<ErrorKind>,
 }
 
-fn centered_origin(cols: u16, rows: u16) -> Result<Offset> {
-    let (w, h) = terminal::size()?;
+fn centered_origin<B: Backend>(backend: &B, cols: u16, rows: u16) -> Result<Offset> {
+    let (w, h) = backend.size()?;
+    // Account for the frame drawn around the game
+    let (cols, rows) = (cols + 2, rows + 2);
     let margin_x = if w > cols { (w - cols) / 2 } else { 0 };
 
     let margin_y = if h > rows { (h - rows) / 2 } else { 0 };
@@ -75,55 +197,154 @@ fn centered_origin(cols: u16, rows: u16) -> Result<Offset> {
     Ok(Offset::new(margin_x + 1, margin_y + 1))
 }
 
-impl<TGame> Default for RogueCrossGame<TGame>
+impl<TGame, TBackend> Default for RogueCrossGame<TGame, TBackend>
 where
     TGame: Game,
+    TBackend: Backend + Default,
 {
     fn default() -> Self {
+        Self::with_backend(Default::default())
+    }
+}
+
+impl<TGame, TBackend> RogueCrossGame<TGame, TBackend>
+where
+    TGame: Game,
+    TBackend: Backend,
+{
+    pub fn with_backend(backend: TBackend) -> Self {
         let mut ecs = World::new();
         ecs.register::<Position>();
         ecs.register::<Renderable>();
+        ecs.register::<RenderLayer>();
         ecs.register::<Collider>();
         ecs.register::<Player>();
+        ecs.register::<Viewshed>();
+        ecs.register::<Monster>();
+        ecs.register::<Name>();
+        ecs.register::<CombatStats>();
+        ecs.register::<WantsToMelee>();
+        ecs.register::<SufferDamage>();
+
+        let game = TGame::default();
+        game.register(&mut ecs);
+
         let game_state = GameState {
             cols: 80,
             rows: 25,
             event: None,
+            action: None,
         };
-        let stdout: Stdout = stdout();
-        let map = create_blank_map(&game_state);
+        ecs.insert(create_blank_map(&game_state));
+        ecs.insert(SpatialIndex::new(game_state.cols, game_state.rows));
+        ecs.insert(Camera::default());
+        // A malformed seed is reported once the game starts unless the game sets its own
+        let seed_error = match GameRng::from_args_or_entropy() {
+            Ok(rng) => {
+                ecs.insert(rng);
+                None
+            }
+            Err(e) => {
+                ecs.insert(GameRng::new(rand::random()));
+                Some(e)
+            }
+        };
+        ecs.insert(RunState::default());
+        ecs.insert(KeyMap::default());
+        ecs.insert(MovementRules::default());
+        ecs.insert(GameLog::default());
+        ecs.insert(Hud::default());
         let player_start_position = Offset { x: 40, y: 12 };
 
         Self {
             ecs,
-            map,
-            game: Default::default(),
+            game,
             game_state,
             player_start_position,
+            player_viewshed: None,
+            log_rows: LOG_ROWS,
+            show_hud: true,
             millis_per_frame: MS_PER_FRAME,
             renderer: None,
+            dispatcher: None,
+            turn_dispatcher: None,
             should_exit: false,
-            stdout,
+            backend,
             title: "Rogue Cross Game".to_string(),
             built_map: false,
             started: false,
+            too_small: false,
+            seed_error,
         }
     }
-}
 
-impl<TGame> RogueCrossGame<TGame>
-where
-    TGame: Game,
-{
+    pub fn backend(&self) -> &TBackend {
+        &self.backend
+    }
+
+    pub fn backend_mut(&mut self) -> &mut TBackend {
+        &mut self.backend
+    }
+
+    pub fn ecs(&self) -> &World {
+        &self.ecs
+    }
+
+    pub fn ecs_mut(&mut self) -> &mut World {
+        &mut self.ecs
+    }
+
+    pub fn game(&self) -> &TGame {
+        &self.game
+    }
+
+    pub fn game_state(&self) -> &GameState {
+        &self.game_state
+    }
+
+    /// The renderer is only available once the game was started
+    pub fn renderer(&self) -> Option<&Renderer> {
+        self.renderer.as_ref()
+    }
+
+    /// The map builder may move the player start position, i.e. to place it inside a room
     pub fn build_map(
         &mut self,
-        create_map: fn(gs: &GameState, player_position: &Offset) -> Vec<TileType>,
+        create_map: fn(gs: &GameState, player_position: &mut Offset, rng: &mut GameRng) -> Map,
     ) {
         assert!(!self.started, "Need to build map before starting the game");
-        self.map = create_map(&self.game_state, &self.player_start_position).to_vec();
+        let map = {
+            let mut rng = self.ecs.write_resource::<GameRng>();
+            create_map(&self.game_state, &mut self.player_start_position, &mut rng)
+        };
+        self.ecs.insert(map);
         self.built_map = true;
     }
 
+    /// Overrides the seed passed on the command line or picked randomly, even a malformed one
+    pub fn set_seed(&mut self, seed: u64) {
+        assert!(!self.started, "Need to set seed before starting the game");
+        assert!(!self.built_map, "Need to set seed before building the map");
+        self.ecs.insert(GameRng::new(seed));
+        self.seed_error = None;
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.ecs.fetch::<GameRng>().seed()
+    }
+
+    pub fn set_keymap(&mut self, keymap: KeyMap) {
+        self.ecs.insert(keymap);
+    }
+
+    pub fn set_movement_rules(&mut self, rules: MovementRules) {
+        self.ecs.insert(rules);
+    }
+
+    pub fn set_camera(&mut self, camera: Camera) {
+        self.ecs.insert(camera);
+    }
+
     pub fn set_player_start(&mut self, pos: Offset) {
         assert!(
             !self.started,
@@ -136,19 +357,44 @@ where
         self.player_start_position = pos;
     }
 
+    /// Gives the player a `Viewshed` with the given range so only what they see is rendered
+    pub fn set_player_viewshed(&mut self, range: i32) {
+        assert!(
+            !self.started,
+            "Need to set player viewshed before starting the game"
+        );
+        self.player_viewshed = Some(range);
+    }
+
+    /// Rows of the `GameLog` panel beneath the play area, `0` to hide it
+    pub fn set_log_rows(&mut self, rows: u16) {
+        assert!(
+            !self.started,
+            "Need to set log rows before starting the game"
+        );
+        self.log_rows = rows;
+    }
+
+    /// Whether to show the status bar beneath the play area
+    pub fn set_hud_visible(&mut self, visible: bool) {
+        assert!(
+            !self.started,
+            "Need to set hud visibility before starting the game"
+        );
+        self.show_hud = visible;
+    }
+
     pub fn start(&mut self) -> Result<()> {
+        println!("Seed: {}", self.seed());
+        let _guard = TerminalGuard::<TBackend>::new();
         self.init()?;
 
         loop {
             let loop_start = SystemTime::now();
 
-            self.poll()?;
-            self.update()?;
-
-            if self.should_exit {
+            if !self.step()? {
                 break;
             }
-            self.render()?;
 
             self.enforce_framerate(&loop_start);
         }
@@ -156,49 +402,126 @@ where
         self.deinit()
     }
 
+    /// Runs a single frame, returns `false` once the game should exit
+    fn step(&mut self) -> Result<bool> {
+        self.poll()?;
+        if let Some(Event::Resize(_, _)) = self.game_state.event {
+            self.resize()?;
+        }
+        if self.too_small {
+            // The game is paused until the terminal fits it again, but it can still be quit
+            if self.map_event() == Some(Action::Quit) {
+                self.should_exit = true;
+            }
+        } else {
+            self.update()?;
+        }
+
+        if self.should_exit {
+            return Ok(false);
+        }
+        self.render()?;
+        Ok(true)
+    }
+
     fn init(&mut self) -> Result<()> {
-        self.init_map_entities();
+        if let Some(e) = self.seed_error.take() {
+            return Err(e);
+        }
+        self.started = true;
+        self.init_dispatcher();
+        self.game
+            .spawn_map_entities(&self.game_state, &mut self.ecs)?;
         self.init_player();
+        self.init_camera();
 
-        enable_raw_mode()?;
+        self.backend.enter(&self.title)?;
+        self.renderer = Some(Renderer::new(
+            Offset::default(),
+            self.game_state.cols,
+            self.screen_rows(),
+        ));
+        self.layout()?;
+
+        self.game.init(&self.game_state, &mut self.ecs)?;
 
-        execute!(
-            self.stdout,
-            terminal::SetTitle(&self.title),
-            terminal::Clear(ClearType::All),
-            cursor::Hide,
-        )?;
+        self.run_systems();
+        self.backend.flush()
+    }
 
+    /// Centers the game inside the terminal and draws its frame.
+    /// Shows a notice instead if the terminal is too small to fit the game including its frame.
+    fn layout(&mut self) -> Result<()> {
         let cols = self.game_state.cols;
-        let rows = self.game_state.rows;
-        let origin = centered_origin(cols, rows)?;
+        let log_row = self.log_row();
+        let screen_rows = self.screen_rows();
+
+        let size = self.backend.size()?;
+        let required = (cols + 2, screen_rows + 2);
+        self.too_small = size.0 < required.0 || size.1 < required.1;
+        if self.too_small {
+            if size.0 == 0 || size.1 == 0 {
+                return Ok(());
+            }
+            let mut notice = Renderer::new(Offset::default(), size.0, size.1);
+            draw_too_small_notice(&mut notice, required);
+            return notice.flush(&mut self.backend);
+        }
+
+        let origin = centered_origin(&self.backend, cols, screen_rows)?;
+        // The frame is static, so it is only drawn here, after the screen was cleared
+        let mut frame = Renderer::new(
+            origin.translate(&Offset::new(-1, -1)),
+            cols + 2,
+            screen_rows + 2,
+        );
+        let separator_row = if self.log_rows > 0 {
+            Some(log_row)
+        } else {
+            None
+        };
+        draw_terminal_frame(&mut frame, separator_row);
+        frame.flush(&mut self.backend)?;
 
-        draw_terminal_frame(&mut self.stdout, &origin, cols as u16, rows as u16)?;
+        let renderer = self.renderer.as_mut().unwrap();
+        renderer.set_origin(origin);
+        // The screen was cleared, so everything needs to be drawn again
+        renderer.invalidate();
+        Ok(())
+    }
 
-        self.renderer = Some(Renderer::new(origin, cols, rows));
+    fn resize(&mut self) -> Result<()> {
+        self.backend.clear()?;
+        self.layout()?;
+        self.backend.flush()
+    }
 
-        self.game.init(&self.game_state, &mut self.ecs)?;
+    /// Rows of the play area plus the status bar and message panel beneath it
+    fn screen_rows(&self) -> u16 {
+        if self.log_rows > 0 {
+            self.log_row() + 1 + self.log_rows
+        } else {
+            self.log_row()
+        }
+    }
 
-        self.ecs.maintain();
-        self.stdout.flush()?;
-        Ok(())
+    /// Row of the separator above the message panel
+    fn log_row(&self) -> u16 {
+        if self.show_hud {
+            self.game_state.rows + HUD_ROWS
+        } else {
+            self.game_state.rows
+        }
     }
 
     fn deinit(&mut self) -> Result<()> {
-        execute!(
-            self.stdout,
-            terminal::Clear(ClearType::All),
-            cursor::MoveTo(0, 0),
-            cursor::Show,
-        )?;
-        disable_raw_mode()
+        self.backend.leave()
     }
 
     fn poll(&mut self) -> Result<()> {
-        self.game_state.event = None;
-        if poll(Duration::from_millis(self.millis_per_frame))? {
-            self.game_state.event = Some(read()?);
-        }
+        self.game_state.event = self
+            .backend
+            .poll_event(Duration::from_millis(self.millis_per_frame))?;
         Ok(())
     }
 
@@ -212,34 +535,9 @@ where
         }
     }
 
-    fn idx_xy(&self, idx: usize) -> Offset {
-        let x = idx as u16 % self.game_state.cols;
-        let y = idx as u16 / self.game_state.cols;
-        Offset::new(x, y)
-    }
-
-    fn xy_idx(&self, x: usize, y: usize) -> usize {
-        (y * self.game_state.cols as usize) + x
-    }
-
-    fn init_map_entities(&mut self) {
-        for (idx, tile) in self.map.iter().enumerate() {
-            let Offset { x, y } = self.idx_xy(idx);
-            let renderable = match tile {
-                TileType::Floor => renderable_floor(),
-                TileType::Wall => renderable_wall(),
-                TileType::Empty => Renderable::default(),
-            };
-            self.ecs
-                .create_entity()
-                .with(Position { x, y })
-                .with(renderable)
-                .build();
-        }
-    }
-
     fn init_player(&mut self) {
-        self.ecs
+        let mut player = self
+            .ecs
             .create_entity()
             .with::<Position>((&self.player_start_position).into())
             .with(Renderable {
@@ -248,91 +546,340 @@ where
                 bg: None,
             })
             .with(Player {})
-            .build();
+            .with(Collider {});
+        if let Some(range) = self.player_viewshed {
+            player = player.with(Viewshed::new(range));
+        }
+        self.game.build_player(player).build();
+    }
+
+    fn init_dispatcher(&mut self) {
+        let mut builder = DispatcherBuilder::new();
+        self.game.register_systems(&mut builder);
+        let mut dispatcher = builder.build();
+        // Registers components and resources the game systems use
+        dispatcher.setup(&mut self.ecs);
+        self.dispatcher = Some(dispatcher);
+
+        let mut builder = DispatcherBuilder::new();
+        self.game.register_turn_systems(&mut builder);
+        let mut turn_dispatcher = builder.build();
+        turn_dispatcher.setup(&mut self.ecs);
+        self.turn_dispatcher = Some(turn_dispatcher);
+    }
+
+    fn init_camera(&mut self) {
+        let map = self.ecs.fetch::<Map>();
+        let mut camera = self.ecs.write_resource::<Camera>();
+        let start = Position::from(&self.player_start_position);
+        camera.center_on(&start, &map, self.game_state.cols, self.game_state.rows);
     }
 
     //
     // Rendering
     //
     fn render(&mut self) -> Result<()> {
-        let out = &mut self.stdout;
+        if self.too_small {
+            return Ok(());
+        }
+        let log_row = self.log_row() as i32;
+        let backend = &mut self.backend;
         let renderer = self.renderer.as_mut().unwrap();
 
-        let positions = self.ecs.read_storage::<Position>();
-        let renderables = self.ecs.read_storage::<Renderable>();
+        // The resources are released before the game renders, so it can fetch any of them
+        {
+            let map = self.ecs.fetch::<Map>();
+            let mut camera = self.ecs.write_resource::<Camera>();
+            let positions = self.ecs.read_storage::<Position>();
+            let renderables = self.ecs.read_storage::<Renderable>();
+            let layers = self.ecs.read_storage::<RenderLayer>();
+            let players = self.ecs.read_storage::<Player>();
+            let viewsheds = self.ecs.read_storage::<Viewshed>();
+
+            // Without a player viewshed the entire map is visible
+            let fov = (&players, &viewsheds).join().next().is_some();
+
+            let cols = self.game_state.cols;
+            let rows = self.game_state.rows;
+            if let Some((pos, _)) = (&positions, &players).join().next() {
+                camera.follow(pos, &map, cols, rows);
+            }
+
+            renderer.clear();
+
+            // Terrain is the base layer, entities are drawn on top of it by their `RenderLayer`
+            for y in 0..rows as i32 {
+                for x in 0..cols as i32 {
+                    let (wx, wy) = camera.screen_to_world(x, y);
+                    let tile = match map.tile(wx, wy) {
+                        Some(tile) => tile,
+                        None => continue,
+                    };
+                    if !fov || map.is_visible(wx, wy) {
+                        renderer.render(x, y, &renderable_tile(tile));
+                    } else if map.is_revealed(wx, wy) {
+                        renderer.render(x, y, &renderable_tile_remembered(tile));
+                    }
+                }
+            }
+
+            let mut drawables = vec![];
+            for (pos, render, layer) in (&positions, &renderables, layers.maybe()).join() {
+                let (x, y) = camera.world_to_screen(pos.x, pos.y);
+                if 0 > x || x >= cols as i32 || 0 > y || y >= rows as i32 {
+                    continue;
+                }
+                if fov && !map.is_visible(pos.x, pos.y) {
+                    continue;
+                }
+                drawables.push((layer.copied().unwrap_or_default(), x, y, render));
+            }
+            // Joins run in entity order and the sort is stable, so entities sharing a layer and tile
+            // are always drawn in the same order
+            drawables.sort_by_key(|(layer, ..)| *layer);
+            for (_, x, y, render) in drawables {
+                renderer.compose(x, y, render);
+            }
 
-        for (pos, render) in (&positions, &renderables).join() {
-            if 0 > pos.x
-                || pos.x >= self.game_state.cols as i32
-                || 0 > pos.y
-                || pos.y >= self.game_state.rows as i32
-            {
-                continue;
+            let log = self.ecs.fetch::<GameLog>();
+            if self.show_hud {
+                let hud = self.ecs.fetch::<Hud>();
+                let combat_stats = self.ecs.read_storage::<CombatStats>();
+                let player_stats = (&players, &combat_stats).join().next().map(|(_, s)| s);
+                render_hud(
+                    renderer,
+                    rows as i32,
+                    &hud,
+                    player_stats,
+                    map.depth,
+                    log.turn(),
+                );
+            }
+            if self.log_rows > 0 {
+                render_log(renderer, &log, log_row, self.log_rows);
             }
-            renderer.render(pos.x, pos.y, render);
         }
+        self.game.render(&self.game_state, &self.ecs, renderer);
 
-        renderer.flush(out)
+        renderer.flush(backend)
     }
 
     //
     // Updates
     //
     fn update(&mut self) -> Result<()> {
-        self.process_input();
-        self.game.update(&self.game_state, &mut self.ecs)?;
+        let player_acted = self.process_input()?;
+        self.run_turn(player_acted)?;
+        self.game.update(&self.game_state, &self.ecs)?;
+        if let Some(dispatcher) = self.dispatcher.as_mut() {
+            dispatcher.dispatch(&self.ecs);
+        }
+        self.run_systems();
+        Ok(())
+    }
+
+    /// A player action runs the player and monster turns right away within the same frame
+    fn run_turn(&mut self, player_acted: bool) -> Result<()> {
+        if player_acted {
+            self.ecs.write_resource::<GameLog>().next_turn();
+            self.set_run_state(RunState::PlayerTurn);
+        }
+        if self.run_state() == RunState::PlayerTurn {
+            self.game.player_turn(&self.game_state, &self.ecs)?;
+            self.run_systems();
+            self.advance_run_state(RunState::PlayerTurn, RunState::MonsterTurn);
+        }
+        if self.run_state() == RunState::MonsterTurn {
+            let mut monster_ai = MonsterAiSystem {};
+            monster_ai.run_now(&self.ecs);
+            if let Some(dispatcher) = self.turn_dispatcher.as_mut() {
+                dispatcher.dispatch(&self.ecs);
+            }
+            self.game.monster_turn(&self.game_state, &self.ecs)?;
+            self.run_systems();
+            self.advance_run_state(RunState::MonsterTurn, RunState::AwaitingInput);
+        }
         Ok(())
     }
 
-    fn process_input(&mut self) {
-        match self.game_state.event {
-            Some(Event::Key(KeyEvent {
-                modifiers: KeyModifiers::NONE,
-                code,
-            })) => match code {
-                KeyCode::Left | KeyCode::Char('a') => self.move_player(-1, 0),
-                KeyCode::Right | KeyCode::Char('d') => self.move_player(1, 0),
-                KeyCode::Up | KeyCode::Char('w') => self.move_player(0, -1),
-                KeyCode::Down | KeyCode::Char('s') => self.move_player(0, 1),
-                KeyCode::Esc => self.should_exit = true,
-                _ => {}
-            },
-            _ => {}
+    fn run_state(&self) -> RunState {
+        *self.ecs.fetch::<RunState>()
+    }
+
+    fn set_run_state(&mut self, run_state: RunState) {
+        *self.ecs.write_resource::<RunState>() = run_state;
+    }
+
+    /// Keeps the state if it was changed during the turn, i.e. to `RunState::GameOver`
+    fn advance_run_state(&mut self, from: RunState, to: RunState) {
+        if self.run_state() == from {
+            self.set_run_state(to);
         }
     }
 
-    fn move_by(&self, pos: &mut Position, dx: i32, dy: i32) {
-        let x = pos.x + dx;
-        let y = pos.y + dy;
-        let idx = self.xy_idx(x as usize, y as usize);
-        let blocked = match self.map[idx] {
-            TileType::Wall => true,
-            TileType::Empty | TileType::Floor => false,
+    fn run_systems(&mut self) {
+        let mut melee = MeleeCombatSystem {};
+        melee.run_now(&self.ecs);
+        let mut damage = DamageSystem {};
+        damage.run_now(&self.ecs);
+        let mut cleanup = DeadCleanupSystem {};
+        cleanup.run_now(&self.ecs);
+        self.ecs.maintain();
+        let mut map_indexing = MapIndexingSystem {};
+        map_indexing.run_now(&self.ecs);
+        let mut visibility = VisibilitySystem {};
+        visibility.run_now(&self.ecs);
+        self.ecs.maintain();
+    }
+
+    /// Sets the action the `KeyMap` binds to the polled event, if any
+    fn map_event(&mut self) -> Option<Action> {
+        self.game_state.action = match self.game_state.event {
+            Some(Event::Key(key)) => self.ecs.fetch::<KeyMap>().action_for(&key),
+            _ => None,
+        };
+        self.game_state.action
+    }
+
+    /// Returns `true` if the player took an action that ends their turn
+    fn process_input(&mut self) -> Result<bool> {
+        let action = match self.map_event() {
+            Some(action) => action,
+            None => return Ok(false),
+        };
+
+        let awaiting_input = self.run_state() == RunState::AwaitingInput;
+        match self
+            .game
+            .handle_action(action, &self.game_state, &self.ecs)?
+        {
+            ActionOutcome::Handled => return Ok(false),
+            ActionOutcome::TookTurn => return Ok(awaiting_input),
+            ActionOutcome::Unhandled => {}
+        }
+
+        let took_turn = match action {
+            Action::Quit => {
+                self.should_exit = true;
+                false
+            }
+            Action::ScrollLogUp => {
+                let rows = self.log_rows as usize;
+                self.ecs.write_resource::<GameLog>().scroll_up(rows, rows);
+                false
+            }
+            Action::ScrollLogDown => {
+                let rows = self.log_rows as usize;
+                self.ecs.write_resource::<GameLog>().scroll_down(rows);
+                false
+            }
+            _ if !awaiting_input => false,
+            Action::Move(dir) => {
+                let Offset { x, y } = dir.delta();
+                match self.move_player(x, y) {
+                    Some((_, MoveOutcome::Moved)) => true,
+                    Some((player, MoveOutcome::Bumped(target))) => {
+                        let outcome =
+                            self.game
+                                .bump(player, target, &self.game_state, &self.ecs)?;
+                        match outcome {
+                            ActionOutcome::Unhandled => self.melee(player, target),
+                            _ => outcome == ActionOutcome::TookTurn,
+                        }
+                    }
+                    Some((_, MoveOutcome::Blocked)) | None => false,
+                }
+            }
+            Action::Wait => true,
+            Action::PickUp | Action::Custom(_) => false,
         };
-        if !blocked {
-            pos.x = x;
-            pos.y = y;
-            self.clamp_position(pos)
+        Ok(took_turn)
+    }
+
+    /// Makes the player attack the target if it is a monster and both can fight.
+    /// Returns `true` if the attack ends the player's turn.
+    fn melee(&self, player: Entity, target: Entity) -> bool {
+        let monsters = self.ecs.read_storage::<Monster>();
+        let stats = self.ecs.read_storage::<CombatStats>();
+        let hostile = monsters.contains(target) && stats.contains(target);
+        if !hostile || !stats.contains(player) {
+            return false;
         }
+        self.ecs
+            .write_storage::<WantsToMelee>()
+            .insert(player, WantsToMelee { target })
+            .expect("Unable to insert attack");
+        true
     }
 
-    fn move_player(&self, dx: i32, dy: i32) {
+    fn move_player(&self, dx: i32, dy: i32) -> Option<(Entity, MoveOutcome)> {
+        let entities = self.ecs.entities();
         let mut positions = self.ecs.write_storage::<Position>();
+        let mut viewsheds = self.ecs.write_storage::<Viewshed>();
         let players = self.ecs.read_storage::<Player>();
-
-        let player_positions = (&players, &mut positions).join();
-
-        for (_, pos) in player_positions {
-            self.move_by(pos, dx, dy)
+        let mut map = self.ecs.write_resource::<Map>();
+        let mut index = self.ecs.write_resource::<SpatialIndex>();
+        let rules = self.ecs.fetch::<MovementRules>();
+
+        let player_positions = (
+            &entities,
+            &players,
+            &mut positions,
+            (&mut viewsheds).maybe(),
+        )
+            .join();
+
+        let mut result = None;
+        for (entity, _, pos, viewshed) in player_positions {
+            let outcome = move_by(&mut map, &mut index, &rules, entity, pos, dx, dy);
+            if outcome == MoveOutcome::Moved {
+                if let Some(viewshed) = viewshed {
+                    viewshed.dirty = true;
+                }
+            }
+            result = result.or(Some((entity, outcome)));
         }
+        result
     }
+}
 
-    fn clamp_position(&self, pos: &mut Position) {
-        let minx = 0;
-        let maxx = self.game_state.cols as i32 - 1;
-        let miny = 0;
-        let maxy = self.game_state.rows as i32 - 1;
-        pos.clamp(minx, maxx, miny, maxy)
+/// Moves the entity by the delta unless the terrain or another entity with a `Collider` is in the way.
+/// Keeps the occupants of the `Map` and the `SpatialIndex` up to date, so use it to move entities
+/// within a turn.
+pub fn move_by(
+    map: &mut Map,
+    index: &mut SpatialIndex,
+    rules: &MovementRules,
+    entity: Entity,
+    pos: &mut Position,
+    dx: i32,
+    dy: i32,
+) -> MoveOutcome {
+    let from = Offset::from(&*pos);
+    if !can_step(map, rules, from, dx, dy) {
+        return MoveOutcome::Blocked;
+    }
+    let x = pos.x + dx;
+    let y = pos.y + dy;
+    match map.occupant(x, y) {
+        Some(occupant) if occupant != entity => return MoveOutcome::Bumped(occupant),
+        _ => {}
     }
+    map.move_occupant(entity, pos.x, pos.y, x, y);
+    pos.x = x;
+    pos.y = y;
+    index.move_entity(entity, from, Offset::from(&*pos));
+    MoveOutcome::Moved
 }
 
+/// Whether the terrain and the `MovementRules` allow a step by the delta, ignoring colliders
+pub fn can_step(map: &Map, rules: &MovementRules, from: Offset, dx: i32, dy: i32) -> bool {
+    if map.is_terrain_blocked(from.x + dx, from.y + dy) {
+        return false;
+    }
+    let diagonal = dx != 0 && dy != 0;
+    !(diagonal
+        && rules.block_diagonal_squeeze
+        && map.is_terrain_blocked(from.x + dx, from.y)
+        && map.is_terrain_blocked(from.x, from.y + dy))
+}
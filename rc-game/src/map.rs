@@ -0,0 +1,145 @@
+use specs::Entity;
+
+use crate::{Offset, Rect, TileType};
+
+/// The terrain of the game, available as a resource in the `World`
+#[derive(Clone)]
+pub struct Map {
+    pub width: u16,
+    pub height: u16,
+    pub tiles: Vec<TileType>,
+    /// Tiles the player has seen at some point
+    pub revealed_tiles: Vec<bool>,
+    /// Tiles the player currently sees
+    pub visible_tiles: Vec<bool>,
+    /// Rooms carved by the map builder, empty for maps that have none
+    pub rooms: Vec<Rect>,
+    /// Entity with a `Collider` on each tile, rebuilt by the `MapIndexingSystem`.
+    /// Only keeps the last collider indexed on a tile, the `SpatialIndex` has all entities.
+    pub occupants: Vec<Option<Entity>>,
+    /// Dungeon level shown in the status bar, starting at 1
+    pub depth: i32,
+}
+
+impl Map {
+    /// Map of the given size with all tiles `TileType::Empty`
+    pub fn new(width: u16, height: u16) -> Self {
+        Self::filled(width, height, TileType::Empty)
+    }
+
+    pub fn filled(width: u16, height: u16, tile: TileType) -> Self {
+        let tiles = vec![tile; width as usize * height as usize];
+        Self::from_tiles(width, height, tiles)
+    }
+
+    pub fn from_tiles(width: u16, height: u16, tiles: Vec<TileType>) -> Self {
+        assert_eq!(
+            tiles.len(),
+            width as usize * height as usize,
+            "Tiles need to cover the entire map"
+        );
+        let len = tiles.len();
+        Self {
+            width,
+            height,
+            tiles,
+            revealed_tiles: vec![false; len],
+            visible_tiles: vec![false; len],
+            rooms: vec![],
+            occupants: vec![None; len],
+            depth: 1,
+        }
+    }
+
+    pub fn xy_idx(&self, x: i32, y: i32) -> usize {
+        (y as usize * self.width as usize) + x as usize
+    }
+
+    pub fn idx_xy(&self, idx: usize) -> Offset {
+        let x = (idx % self.width as usize) as i32;
+        let y = (idx / self.width as usize) as i32;
+        Offset::new(x, y)
+    }
+
+    pub fn in_bounds(&self, x: i32, y: i32) -> bool {
+        0 <= x && x < self.width as i32 && 0 <= y && y < self.height as i32
+    }
+
+    pub fn tile(&self, x: i32, y: i32) -> Option<TileType> {
+        if self.in_bounds(x, y) {
+            Some(self.tiles[self.xy_idx(x, y)])
+        } else {
+            None
+        }
+    }
+
+    /// Sets the tile if inside the map, returns `false` otherwise
+    pub fn set_tile(&mut self, x: i32, y: i32, tile: TileType) -> bool {
+        if !self.in_bounds(x, y) {
+            return false;
+        }
+        let idx = self.xy_idx(x, y);
+        self.tiles[idx] = tile;
+        true
+    }
+
+    /// Whether the tile blocks sight, tiles outside the map do
+    pub fn is_opaque(&self, x: i32, y: i32) -> bool {
+        match self.tile(x, y) {
+            None | Some(TileType::Wall) => true,
+            Some(TileType::Empty) | Some(TileType::Floor) => false,
+        }
+    }
+
+    pub fn is_visible(&self, x: i32, y: i32) -> bool {
+        self.in_bounds(x, y) && self.visible_tiles[self.xy_idx(x, y)]
+    }
+
+    pub fn is_revealed(&self, x: i32, y: i32) -> bool {
+        self.in_bounds(x, y) && self.revealed_tiles[self.xy_idx(x, y)]
+    }
+
+    /// Blocked by terrain or an entity with a `Collider`, tiles outside the map are blocked
+    pub fn is_blocked(&self, x: i32, y: i32) -> bool {
+        self.is_terrain_blocked(x, y) || self.occupant(x, y).is_some()
+    }
+
+    /// Blocked by terrain only, tiles outside the map are blocked
+    pub fn is_terrain_blocked(&self, x: i32, y: i32) -> bool {
+        match self.tile(x, y) {
+            None | Some(TileType::Wall) => true,
+            Some(TileType::Empty) | Some(TileType::Floor) => false,
+        }
+    }
+
+    /// The entity with a `Collider` on the tile if any, the last one if there are several
+    pub fn occupant(&self, x: i32, y: i32) -> Option<Entity> {
+        if self.in_bounds(x, y) {
+            self.occupants[self.xy_idx(x, y)]
+        } else {
+            None
+        }
+    }
+
+    pub fn clear_occupants(&mut self) {
+        for occupant in self.occupants.iter_mut() {
+            *occupant = None;
+        }
+    }
+
+    pub fn set_occupant(&mut self, x: i32, y: i32, entity: Entity) {
+        if self.in_bounds(x, y) {
+            let idx = self.xy_idx(x, y);
+            self.occupants[idx] = Some(entity);
+        }
+    }
+
+    /// Keeps the index up to date when an entity moves within a turn
+    pub fn move_occupant(&mut self, entity: Entity, from_x: i32, from_y: i32, x: i32, y: i32) {
+        if self.occupant(from_x, from_y) == Some(entity) {
+            let idx = self.xy_idx(from_x, from_y);
+            self.occupants[idx] = None;
+            self.set_occupant(x, y, entity);
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use rand::Rng;
+use std::cmp::{max, min};
+
+use crate::{GameRng, GameState, Map, Offset, Rect, TileType};
+
+/// Carves non-overlapping rectangular rooms out of solid rock and connects each room
+/// to the previous one with an L-shaped corridor
+pub struct RoomsAndCorridors {
+    pub width: u16,
+    pub height: u16,
+    pub max_rooms: usize,
+    pub min_size: i32,
+    pub max_size: i32,
+}
+
+impl RoomsAndCorridors {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            max_rooms: 30,
+            min_size: 6,
+            max_size: 10,
+        }
+    }
+
+    /// Builds the map, its `rooms` hold the carved rooms in the order they were created
+    pub fn build<R: Rng>(&self, rng: &mut R) -> Map {
+        let mut map = Map::filled(self.width, self.height, TileType::Wall);
+        // Rooms span up to x + w and y + h, keep them off the outer border
+        let max_x = self.width as i32 - self.max_size - 2;
+        let max_y = self.height as i32 - self.max_size - 2;
+        if max_x < 1 || max_y < 1 {
+            return map;
+        }
+
+        for _ in 0..self.max_rooms {
+            let w = rng.gen_range(self.min_size, self.max_size + 1);
+            let h = rng.gen_range(self.min_size, self.max_size + 1);
+            let x = rng.gen_range(1, self.width as i32 - w - 1);
+            let y = rng.gen_range(1, self.height as i32 - h - 1);
+            let room = Rect::new(x, y, w, h);
+            if map.rooms.iter().any(|other| room.intersects(other)) {
+                continue;
+            }
+
+            apply_room(&mut map, &room);
+            if let Some(prev) = map.rooms.last() {
+                let Offset { x: new_x, y: new_y } = room.center();
+                let Offset {
+                    x: prev_x,
+                    y: prev_y,
+                } = prev.center();
+                if rng.gen::<bool>() {
+                    apply_horizontal_tunnel(&mut map, prev_x, new_x, prev_y);
+                    apply_vertical_tunnel(&mut map, prev_y, new_y, new_x);
+                } else {
+                    apply_vertical_tunnel(&mut map, prev_y, new_y, prev_x);
+                    apply_horizontal_tunnel(&mut map, prev_x, new_x, new_y);
+                }
+            }
+            map.rooms.push(room);
+        }
+        map
+    }
+}
+
+/// Rooms and corridors map the size of the game, places the player in the center of the first room.
+/// Pass it to `RogueCrossGame::build_map`.
+pub fn rooms_and_corridors(gs: &GameState, player_position: &mut Offset, rng: &mut GameRng) -> Map {
+    let map = RoomsAndCorridors::new(gs.cols, gs.rows).build(rng);
+    if let Some(room) = map.rooms.first() {
+        *player_position = room.center();
+    }
+    map
+}
+
+fn apply_room(map: &mut Map, room: &Rect) {
+    for y in room.y1 + 1..=room.y2 {
+        for x in room.x1 + 1..=room.x2 {
+            map.set_tile(x, y, TileType::Floor);
+        }
+    }
+}
+
+fn apply_horizontal_tunnel(map: &mut Map, x1: i32, x2: i32, y: i32) {
+    for x in min(x1, x2)..=max(x1, x2) {
+        map.set_tile(x, y, TileType::Floor);
+    }
+}
+
+fn apply_vertical_tunnel(map: &mut Map, y1: i32, y2: i32, x: i32) {
+    for y in min(y1, y2)..=max(y1, y2) {
+        map.set_tile(x, y, TileType::Floor);
+    }
+}
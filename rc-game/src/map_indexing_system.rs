@@ -0,0 +1,28 @@
+use specs::prelude::*;
+
+use crate::{Collider, Map, Position, SpatialIndex};
+
+/// Rebuilds the per tile index of entities with a `Collider` on the `Map` and the
+/// `SpatialIndex` of all entities with a `Position`
+pub struct MapIndexingSystem {}
+
+impl<'a> System<'a> for MapIndexingSystem {
+    type SystemData = (
+        WriteExpect<'a, Map>,
+        WriteExpect<'a, SpatialIndex>,
+        Entities<'a>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, Collider>,
+    );
+
+    fn run(&mut self, (mut map, mut index, entities, positions, colliders): Self::SystemData) {
+        map.clear_occupants();
+        index.reset(map.width, map.height);
+        for (entity, pos, collider) in (&entities, &positions, colliders.maybe()).join() {
+            index.insert(pos.x, pos.y, entity);
+            if collider.is_some() {
+                map.set_occupant(pos.x, pos.y, entity);
+            }
+        }
+    }
+}
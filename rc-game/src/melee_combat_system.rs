@@ -0,0 +1,69 @@
+use crossterm::style::Color;
+use specs::prelude::*;
+
+use crate::{CombatStats, GameLog, Name, Player, SufferDamage, WantsToMelee};
+
+/// Resolves melee intents of living attackers against living targets.
+/// The damage is the attacker's power reduced by the target's defense.
+pub struct MeleeCombatSystem {}
+
+impl<'a> System<'a> for MeleeCombatSystem {
+    type SystemData = (
+        Entities<'a>,
+        WriteStorage<'a, WantsToMelee>,
+        ReadStorage<'a, CombatStats>,
+        WriteStorage<'a, SufferDamage>,
+        ReadStorage<'a, Name>,
+        ReadStorage<'a, Player>,
+        WriteExpect<'a, GameLog>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (entities, mut wants_melee, combat_stats, mut damage, names, players, mut log) = data;
+
+        for (entity, wants_melee, stats) in (&entities, &wants_melee, &combat_stats).join() {
+            if stats.hp <= 0 {
+                continue;
+            }
+            let target = wants_melee.target;
+            let target_stats = match combat_stats.get(target) {
+                Some(target_stats) if target_stats.hp > 0 => target_stats,
+                _ => continue,
+            };
+            let attacker_name = name_of(entity, &names, &players);
+            let target_name = name_of(target, &names, &players);
+            let amount = i32::max(0, stats.power - target_stats.defense);
+            if amount == 0 {
+                log.push(format!(
+                    "{} is unable to hurt {}.",
+                    attacker_name, target_name
+                ));
+                continue;
+            }
+            let color = if players.contains(target) {
+                Color::Red
+            } else {
+                Color::White
+            };
+            log.push_colored(
+                format!("{} hits {} for {} hp.", attacker_name, target_name, amount),
+                color,
+            );
+            SufferDamage::new_damage(&mut damage, target, amount);
+        }
+        wants_melee.clear();
+    }
+}
+
+/// The entity's `Name`, falls back to `Player` or `Something` for unnamed entities
+pub(crate) fn name_of(
+    entity: Entity,
+    names: &ReadStorage<Name>,
+    players: &ReadStorage<Player>,
+) -> String {
+    match names.get(entity) {
+        Some(name) => name.name.clone(),
+        None if players.contains(entity) => "Player".to_string(),
+        None => "Something".to_string(),
+    }
+}
@@ -0,0 +1,86 @@
+use specs::prelude::*;
+
+use crate::{
+    a_star_with, can_step, move_by, CombatStats, Map, Monster, MoveOutcome, MovementRules, Offset,
+    PathOptions, Player, Position, SpatialIndex, Viewshed, WantsToMelee,
+};
+
+/// Moves each monster that sees the player one step along the shortest path toward them.
+/// Monsters never walk through other colliders and attack the player once adjacent
+/// if they have `CombatStats`.
+pub struct MonsterAiSystem {}
+
+impl<'a> System<'a> for MonsterAiSystem {
+    type SystemData = (
+        WriteExpect<'a, Map>,
+        WriteExpect<'a, SpatialIndex>,
+        ReadExpect<'a, MovementRules>,
+        Entities<'a>,
+        ReadStorage<'a, Player>,
+        ReadStorage<'a, Monster>,
+        WriteStorage<'a, Viewshed>,
+        WriteStorage<'a, Position>,
+        ReadStorage<'a, CombatStats>,
+        WriteStorage<'a, WantsToMelee>,
+    );
+
+    fn run(&mut self, data: Self::SystemData) {
+        let (
+            mut map,
+            mut index,
+            rules,
+            entities,
+            players,
+            monsters,
+            mut viewsheds,
+            mut positions,
+            combat_stats,
+            mut wants_melee,
+        ) = data;
+
+        let (player, player_pos) = match (&entities, &players, &positions).join().next() {
+            Some((player, _, pos)) => (player, Offset::from(pos)),
+            None => return,
+        };
+        let options = PathOptions {
+            avoid_occupied: true,
+            block_diagonal_squeeze: rules.block_diagonal_squeeze,
+            ..Default::default()
+        };
+
+        for (entity, _, viewshed, pos) in
+            (&entities, &monsters, &mut viewsheds, &mut positions).join()
+        {
+            if !viewshed.visible_tiles.contains(&player_pos) {
+                continue;
+            }
+            let from = Offset::from(&*pos);
+            let (dx, dy) = (player_pos.x - from.x, player_pos.y - from.y);
+            // Only adjacent if the monster could step onto the player's tile, i.e. not squeezed
+            if dx.abs() <= 1 && dy.abs() <= 1 && can_step(&map, &rules, from, dx, dy) {
+                if combat_stats.contains(entity) {
+                    wants_melee
+                        .insert(entity, WantsToMelee { target: player })
+                        .expect("Unable to insert attack");
+                }
+                continue;
+            }
+            let next = match a_star_with(&map, from, player_pos, &options) {
+                Some(path) if path.len() > 1 => path[0],
+                _ => continue,
+            };
+            let outcome = move_by(
+                &mut map,
+                &mut index,
+                &rules,
+                entity,
+                pos,
+                next.x - from.x,
+                next.y - from.y,
+            );
+            if outcome == MoveOutcome::Moved {
+                viewshed.dirty = true;
+            }
+        }
+    }
+}
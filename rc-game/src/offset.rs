@@ -2,7 +2,7 @@ use std::fmt::Display;
 
 use crate::components::Position;
 
-#[derive(Default)]
+#[derive(Default, Clone, Copy, Debug, PartialEq)]
 pub struct Offset {
     pub x: i32,
     pub y: i32,
@@ -0,0 +1,244 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::{Map, Offset};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PathOptions {
+    pub connectivity: Connectivity,
+    /// Treat tiles occupied by an entity with a `Collider` as blocked, except the start and goal
+    pub avoid_occupied: bool,
+    /// Don't step diagonally between two walls, see `MovementRules`
+    pub block_diagonal_squeeze: bool,
+}
+
+impl Default for PathOptions {
+    fn default() -> Self {
+        Self {
+            connectivity: Connectivity::Eight,
+            avoid_occupied: false,
+            block_diagonal_squeeze: false,
+        }
+    }
+}
+
+const FOUR: [(i32, i32); 4] = [(0, -1), (0, 1), (1, 0), (-1, 0)];
+const EIGHT: [(i32, i32); 8] = [
+    (0, -1),
+    (0, 1),
+    (1, 0),
+    (-1, 0),
+    (1, -1),
+    (-1, -1),
+    (1, 1),
+    (-1, 1),
+];
+
+impl PathOptions {
+    fn deltas(&self) -> &'static [(i32, i32)] {
+        match self.connectivity {
+            Connectivity::Four => &FOUR,
+            Connectivity::Eight => &EIGHT,
+        }
+    }
+
+    /// Orthogonal steps cost 2 and diagonal ones 3 which keeps paths straight where possible
+    fn step_cost(&self, from: Offset, to: Offset) -> u32 {
+        if from.x != to.x && from.y != to.y {
+            3
+        } else {
+            2
+        }
+    }
+
+    /// Estimated cost between the tiles, never more than the actual cost
+    fn estimate(&self, a: Offset, b: Offset) -> u32 {
+        let dx = (a.x - b.x).unsigned_abs();
+        let dy = (a.y - b.y).unsigned_abs();
+        match self.connectivity {
+            Connectivity::Four => 2 * (dx + dy),
+            Connectivity::Eight => 2 * dx.max(dy) + dx.min(dy),
+        }
+    }
+
+    /// Tiles reachable in one step from `pos`, `always_passable` is never considered occupied
+    fn neighbors(
+        &self,
+        map: &Map,
+        pos: Offset,
+        always_passable: &dyn Fn(Offset) -> bool,
+    ) -> Vec<Offset> {
+        self.deltas()
+            .iter()
+            .filter(|(dx, dy)| {
+                let diagonal = *dx != 0 && *dy != 0;
+                !(diagonal
+                    && self.block_diagonal_squeeze
+                    && map.is_terrain_blocked(pos.x + dx, pos.y)
+                    && map.is_terrain_blocked(pos.x, pos.y + dy))
+            })
+            .map(|(dx, dy)| Offset::new(pos.x + dx, pos.y + dy))
+            .filter(|next| {
+                if map.is_terrain_blocked(next.x, next.y) {
+                    return false;
+                }
+                !self.avoid_occupied
+                    || always_passable(*next)
+                    || map.occupant(next.x, next.y).is_none()
+            })
+            .collect()
+    }
+}
+
+/// Cheapest path using eight way movement, excluding `from` and including `to`.
+/// Orthogonal steps cost 2 and diagonal ones 3, so among paths with the same number of steps
+/// the one with the fewest diagonal steps wins.
+pub fn a_star(map: &Map, from: Offset, to: Offset) -> Option<Vec<Offset>> {
+    a_star_with(map, from, to, &PathOptions::default())
+}
+
+/// Cheapest path, excluding `from` and including `to`, `None` if `to` cannot be reached.
+/// Uses the same step costs as `a_star`.
+pub fn a_star_with(
+    map: &Map,
+    from: Offset,
+    to: Offset,
+    options: &PathOptions,
+) -> Option<Vec<Offset>> {
+    if !map.in_bounds(from.x, from.y) || map.is_terrain_blocked(to.x, to.y) {
+        return None;
+    }
+    let len = map.tiles.len();
+    let mut costs: Vec<Option<u32>> = vec![None; len];
+    let mut came_from: Vec<Option<usize>> = vec![None; len];
+    // Ordered by estimated total cost, then cost so far, then tile index for determinism
+    let mut open = BinaryHeap::new();
+
+    let start = map.xy_idx(from.x, from.y);
+    let goal = map.xy_idx(to.x, to.y);
+    costs[start] = Some(0);
+    open.push(Reverse((options.estimate(from, to), 0, start)));
+
+    let always_passable = |pos: Offset| pos == to;
+    while let Some(Reverse((_, cost, idx))) = open.pop() {
+        if idx == goal {
+            return Some(reconstruct_path(map, &came_from, start, goal));
+        }
+        if costs[idx].is_some_and(|c| c < cost) {
+            continue;
+        }
+        let pos = map.idx_xy(idx);
+        for next in options.neighbors(map, pos, &always_passable) {
+            let next_idx = map.xy_idx(next.x, next.y);
+            let next_cost = cost + options.step_cost(pos, next);
+            if costs[next_idx].is_none_or(|c| next_cost < c) {
+                costs[next_idx] = Some(next_cost);
+                came_from[next_idx] = Some(idx);
+                let estimate = next_cost + options.estimate(next, to);
+                open.push(Reverse((estimate, next_cost, next_idx)));
+            }
+        }
+    }
+    None
+}
+
+fn reconstruct_path(
+    map: &Map,
+    came_from: &[Option<usize>],
+    start: usize,
+    goal: usize,
+) -> Vec<Offset> {
+    let mut path = vec![];
+    let mut idx = goal;
+    while idx != start {
+        path.push(map.idx_xy(idx));
+        idx = came_from[idx].expect("Path should lead back to the start");
+    }
+    path.reverse();
+    path
+}
+
+/// Cost of the cheapest path from each tile to the closest of multiple sources,
+/// using the same step costs as `a_star`
+pub struct DijkstraMap {
+    width: u16,
+    height: u16,
+    values: Vec<Option<u32>>,
+}
+
+impl DijkstraMap {
+    pub fn new(map: &Map, sources: &[Offset], options: &PathOptions) -> Self {
+        Self::with_max_cost(map, sources, options, u32::MAX)
+    }
+
+    /// Stops expanding once paths cost more than `max_cost`, tiles further away are unreachable
+    pub fn with_max_cost(
+        map: &Map,
+        sources: &[Offset],
+        options: &PathOptions,
+        max_cost: u32,
+    ) -> Self {
+        let mut values: Vec<Option<u32>> = vec![None; map.tiles.len()];
+        let mut open = BinaryHeap::new();
+        for source in sources {
+            if map.in_bounds(source.x, source.y) {
+                let idx = map.xy_idx(source.x, source.y);
+                values[idx] = Some(0);
+                open.push(Reverse((0, idx)));
+            }
+        }
+
+        let always_passable = |pos: Offset| sources.contains(&pos);
+        while let Some(Reverse((cost, idx))) = open.pop() {
+            if values[idx].is_some_and(|c| c < cost) {
+                continue;
+            }
+            let pos = map.idx_xy(idx);
+            for next in options.neighbors(map, pos, &always_passable) {
+                let next_idx = map.xy_idx(next.x, next.y);
+                let next_cost = cost.saturating_add(options.step_cost(pos, next));
+                if next_cost > max_cost {
+                    continue;
+                }
+                if values[next_idx].is_none_or(|c| next_cost < c) {
+                    values[next_idx] = Some(next_cost);
+                    open.push(Reverse((next_cost, next_idx)));
+                }
+            }
+        }
+
+        Self {
+            width: map.width,
+            height: map.height,
+            values,
+        }
+    }
+
+    /// Cost to reach the closest source or `None` if no source can be reached
+    pub fn value(&self, x: i32, y: i32) -> Option<u32> {
+        if 0 <= x && x < self.width as i32 && 0 <= y && y < self.height as i32 {
+            self.values[y as usize * self.width as usize + x as usize]
+        } else {
+            None
+        }
+    }
+
+    /// The neighbor of `from` that is closest to a source, `None` if already at one or stuck.
+    /// Only considers moves `options` allow on the `map`, i.e. no diagonal squeezes if blocked.
+    pub fn step_towards(&self, map: &Map, from: Offset, options: &PathOptions) -> Option<Offset> {
+        let current = self.value(from.x, from.y)?;
+        let is_source = |pos: Offset| self.value(pos.x, pos.y) == Some(0);
+        options
+            .neighbors(map, from, &is_source)
+            .into_iter()
+            .filter_map(|pos| self.value(pos.x, pos.y).map(|value| (value, pos)))
+            .filter(|(value, _)| *value < current)
+            .min_by_key(|(value, _)| *value)
+            .map(|(_, pos)| pos)
+    }
+}
@@ -1,57 +1,52 @@
-use crossterm::{cursor, queue, style::Print, style::ResetColor, terminal, Result};
+use crossterm::{cursor, queue, style::Color, style::Print, style::ResetColor, terminal, Result};
 
-use std::io::Write;
+use std::{io::Write, marker::PhantomData, panic, sync::Arc};
 
-use crate::offset::Offset;
+use crate::{backend::Backend, offset::Offset, BoxStyle, Rect, Renderable, Renderer};
 
-const UPPER_LEFT_CORNER: char = '╔';
-const UPPER_RIGHT_CORNER: char = '╗';
-const LOWER_LEFT_CORNER: char = '╚';
-const LOWER_RIGHT_CORNER: char = '╝';
-const VERTICAL_WALL: char = '║';
 const HORIZONTAL_WALL: char = '═';
+const LEFT_JUNCTION: char = '╠';
+const RIGHT_JUNCTION: char = '╣';
 
-/// terminal frame is drawn around what we consider the terminal
-pub fn draw_terminal_frame<W>(w: &mut W, origin: &Offset, ncols: u16, nrows: u16) -> Result<()>
-where
-    W: Write,
-{
-    // Offset terminal origin by -1 to draw it around the actual game
-    let (minc, minr) = origin.apply(-1, -1);
-    let (maxc, maxr) = origin.apply(ncols, nrows);
-
-    // Corners
-    queue!(
-        w,
-        cursor::MoveTo(minc as u16, minr as u16),
-        Print(UPPER_LEFT_CORNER),
-        cursor::MoveTo(maxc as u16, minr as u16),
-        Print(UPPER_RIGHT_CORNER),
-        cursor::MoveTo(maxc as u16, maxr as u16),
-        Print(LOWER_RIGHT_CORNER),
-        cursor::MoveTo(minc as u16, maxr as u16),
-        Print(LOWER_LEFT_CORNER),
-    )?;
+/// Terminal frame drawn around what we consider the terminal, into a renderer that spans the
+/// game plus one cell on each side. The game's `separator_row` gets a line across the frame.
+pub fn draw_terminal_frame(renderer: &mut Renderer, separator_row: Option<u16>) {
+    let cols = renderer.cols() as i32;
+    let rows = renderer.rows() as i32;
+    let frame = Rect {
+        x1: 0,
+        y1: 0,
+        x2: cols - 1,
+        y2: rows - 1,
+    };
+    renderer.draw_box(&frame, BoxStyle::Double, Color::White, None);
 
-    for col in minc + 1..maxc {
-        queue!(
-            w,
-            cursor::MoveTo(col as u16, minr as u16),
-            Print(HORIZONTAL_WALL),
-            cursor::MoveTo(col as u16, maxr as u16),
-            Print(HORIZONTAL_WALL)
-        )?
+    if let Some(row) = separator_row {
+        let y = row as i32 + 1;
+        renderer.hline(1, y, cols - 2, &frame_cell(HORIZONTAL_WALL));
+        renderer.render(0, y, &frame_cell(LEFT_JUNCTION));
+        renderer.render(cols - 1, y, &frame_cell(RIGHT_JUNCTION));
     }
-    for row in minr + 1..maxr {
-        queue!(
-            w,
-            cursor::MoveTo(minc as u16, row as u16),
-            Print(VERTICAL_WALL),
-            cursor::MoveTo(maxc as u16, row as u16),
-            Print(VERTICAL_WALL)
-        )?
+}
+
+/// Tells the user how large the terminal needs to be, into a renderer that spans the terminal
+pub fn draw_too_small_notice(renderer: &mut Renderer, required: (u16, u16)) {
+    let need = format!(
+        "Need {}x{}, have {}x{}",
+        required.0,
+        required.1,
+        renderer.cols(),
+        renderer.rows()
+    );
+    renderer.print(0, 0, "Terminal too small", Color::White, None);
+    renderer.print(0, 1, &need, Color::White, None);
+}
+
+fn frame_cell(glyph: char) -> Renderable {
+    Renderable {
+        glyph,
+        ..Default::default()
     }
-    Ok(())
 }
 
 /// Clear everything except the terminal frame to minimize flicker
@@ -71,8 +66,61 @@ where
             terminal::Clear(terminal::ClearType::UntilNewLine),
             // We cannot avoid clearing the right most column, so we just redraw that afterwards
             cursor::MoveTo(maxc as u16, row as u16),
-            Print(VERTICAL_WALL)
+            Print('║')
         )?
     }
     Ok(())
 }
+
+type PanicHook = dyn Fn(&panic::PanicHookInfo<'_>) + Sync + Send + 'static;
+
+/// Restores the terminal when dropped, including when unwinding or returning early with an error.
+/// While alive it also restores the terminal before a panic message is printed so it is readable.
+pub struct TerminalGuard<B>
+where
+    B: Backend,
+{
+    previous_hook: Arc<PanicHook>,
+    backend: PhantomData<B>,
+}
+
+impl<B> TerminalGuard<B>
+where
+    B: Backend,
+{
+    pub fn new() -> Self {
+        let previous_hook: Arc<PanicHook> = Arc::from(panic::take_hook());
+        let hook = previous_hook.clone();
+        panic::set_hook(Box::new(move |info| {
+            let _ = B::restore();
+            hook(info);
+        }));
+        Self {
+            previous_hook,
+            backend: PhantomData,
+        }
+    }
+}
+
+impl<B> Default for TerminalGuard<B>
+where
+    B: Backend,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<B> Drop for TerminalGuard<B>
+where
+    B: Backend,
+{
+    fn drop(&mut self) {
+        let _ = B::restore();
+        // The panic hook cannot be replaced while panicking
+        if !std::thread::panicking() {
+            let hook = self.previous_hook.clone();
+            panic::set_hook(Box::new(move |info| hook(info)));
+        }
+    }
+}
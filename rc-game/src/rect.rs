@@ -0,0 +1,48 @@
+use std::cmp::{max, min};
+
+use crate::Offset;
+
+/// Rectangle spanning from (x1, y1) to (x2, y2), both inclusive
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rect {
+    pub x1: i32,
+    pub y1: i32,
+    pub x2: i32,
+    pub y2: i32,
+}
+
+impl Rect {
+    pub fn new(x: i32, y: i32, w: i32, h: i32) -> Self {
+        Self {
+            x1: x,
+            y1: y,
+            x2: x + w,
+            y2: y + h,
+        }
+    }
+
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.x1 <= other.x2 && self.x2 >= other.x1 && self.y1 <= other.y2 && self.y2 >= other.y1
+    }
+
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        self.x1 <= x && x <= self.x2 && self.y1 <= y && y <= self.y2
+    }
+
+    pub fn center(&self) -> Offset {
+        Offset::new((self.x1 + self.x2) / 2, (self.y1 + self.y2) / 2)
+    }
+
+    /// Overlapping part of both rectangles if any
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        if !self.intersects(other) {
+            return None;
+        }
+        Some(Rect {
+            x1: max(self.x1, other.x1),
+            y1: max(self.y1, other.y1),
+            x2: min(self.x2, other.x2),
+            y2: min(self.y2, other.y2),
+        })
+    }
+}
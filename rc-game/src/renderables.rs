@@ -1,6 +1,6 @@
 use crossterm::style::Color;
 
-use crate::Renderable;
+use crate::{Renderable, TileType};
 
 pub fn renderable_floor() -> Renderable {
     Renderable {
@@ -17,3 +17,37 @@ pub fn renderable_wall() -> Renderable {
         bg: None,
     }
 }
+
+pub fn renderable_tile(tile: TileType) -> Renderable {
+    match tile {
+        TileType::Floor => renderable_floor(),
+        TileType::Wall => renderable_wall(),
+        TileType::Empty => Renderable::default(),
+    }
+}
+
+/// Tiles the player saw before but can't see right now
+pub fn renderable_tile_remembered(tile: TileType) -> Renderable {
+    let renderable = renderable_tile(tile);
+    Renderable {
+        fg: dim(renderable.fg),
+        bg: renderable.bg.map(dim),
+        ..renderable
+    }
+}
+
+fn dim(color: Color) -> Color {
+    match color {
+        Color::Red => Color::DarkRed,
+        Color::Green => Color::DarkGreen,
+        Color::Yellow => Color::DarkYellow,
+        Color::Blue => Color::DarkBlue,
+        Color::Magenta => Color::DarkMagenta,
+        Color::Cyan => Color::DarkCyan,
+        Color::White => Color::Grey,
+        Color::Grey => Color::DarkGrey,
+        // Darkest grey of the 256 color palette
+        Color::DarkGrey => Color::AnsiValue(236),
+        color => color,
+    }
+}
@@ -1,12 +1,30 @@
-use crate::{offset::Offset, Renderable};
-use crossterm::{
-    cursor, queue, style::Print, style::ResetColor, style::SetBackgroundColor,
-    style::SetForegroundColor, Result,
-};
-use std::io::Write;
+use crate::{backend::Backend, offset::Offset, Rect, Renderable};
+use crossterm::{style::Color, Result};
+
+/// Line glyphs used by `Renderer::draw_box`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BoxStyle {
+    Single,
+    Double,
+    Rounded,
+    Ascii,
+}
+
+impl BoxStyle {
+    /// Upper left, upper right, lower left, lower right, horizontal and vertical glyphs
+    fn glyphs(&self) -> [char; 6] {
+        match self {
+            BoxStyle::Single => ['┌', '┐', '└', '┘', '─', '│'],
+            BoxStyle::Double => ['╔', '╗', '╚', '╝', '═', '║'],
+            BoxStyle::Rounded => ['╭', '╮', '╰', '╯', '─', '│'],
+            BoxStyle::Ascii => ['+', '+', '+', '+', '-', '|'],
+        }
+    }
+}
 
 pub struct Renderer {
-    previous_buffer: Vec<Renderable>,
+    /// What is on screen, `None` where unknown, i.e. after the screen was cleared
+    previous_buffer: Vec<Option<Renderable>>,
     current_buffer: Vec<Renderable>,
     origin: Offset,
     cols: u16,
@@ -15,8 +33,8 @@ pub struct Renderer {
 
 impl Renderer {
     pub fn new(origin: Offset, cols: u16, rows: u16) -> Self {
-        let buffer_size = (cols * rows) as usize;
-        let previous_buffer = vec![Renderable::default(); buffer_size];
+        let buffer_size = cols as usize * rows as usize;
+        let previous_buffer = vec![Some(Renderable::default()); buffer_size];
         let current_buffer = vec![Renderable::default(); buffer_size];
 
         Self {
@@ -28,37 +46,138 @@ impl Renderer {
         }
     }
 
+    /// Where the top left cell is drawn in the terminal
+    pub fn set_origin(&mut self, origin: Offset) {
+        self.origin = origin;
+    }
+
+    /// Forgets what is on screen so the next flush draws every cell
+    pub fn invalidate(&mut self) {
+        for cell in self.previous_buffer.iter_mut() {
+            *cell = None;
+        }
+    }
+
+    /// Resets the current buffer to blank cells, the screen is only updated on the next flush
+    pub fn clear(&mut self) {
+        for cell in self.current_buffer.iter_mut() {
+            *cell = Renderable::default();
+        }
+    }
+
     pub fn render(&mut self, x: i32, y: i32, renderable: &Renderable) {
         let idx = self.xy_idx(x, y);
         self.current_buffer[idx] = renderable.clone()
     }
 
-    pub fn flush<W>(&mut self, w: &mut W) -> Result<()>
+    /// Renders glyph and foreground on top of the cell, keeping its background unless the
+    /// renderable has its own
+    pub fn compose(&mut self, x: i32, y: i32, renderable: &Renderable) {
+        let idx = self.xy_idx(x, y);
+        let cell = &mut self.current_buffer[idx];
+        cell.glyph = renderable.glyph;
+        cell.fg = renderable.fg;
+        if renderable.bg.is_some() {
+            cell.bg = renderable.bg;
+        }
+    }
+
+    /// Prints the text in a single row starting at (x, y), cut off where it leaves the buffer
+    pub fn print(&mut self, x: i32, y: i32, text: &str, fg: Color, bg: Option<Color>) {
+        for (i, glyph) in text.chars().enumerate() {
+            self.set(x + i as i32, y, &Renderable { glyph, fg, bg });
+        }
+    }
+
+    /// Prints the text horizontally centered in row `y`
+    pub fn print_centered(&mut self, y: i32, text: &str, fg: Color, bg: Option<Color>) {
+        let x = (self.cols as i32 - text.chars().count() as i32) / 2;
+        self.print(x, y, text, fg, bg);
+    }
+
+    /// Draws the outline of the rectangle, leaving its inside untouched
+    pub fn draw_box(&mut self, rect: &Rect, style: BoxStyle, fg: Color, bg: Option<Color>) {
+        let [upper_left, upper_right, lower_left, lower_right, horizontal, vertical] =
+            style.glyphs();
+        let cell = |glyph| Renderable { glyph, fg, bg };
+        let Rect { x1, y1, x2, y2 } = *rect;
+
+        self.hline(x1 + 1, y1, x2 - x1 - 1, &cell(horizontal));
+        self.hline(x1 + 1, y2, x2 - x1 - 1, &cell(horizontal));
+        self.vline(x1, y1 + 1, y2 - y1 - 1, &cell(vertical));
+        self.vline(x2, y1 + 1, y2 - y1 - 1, &cell(vertical));
+        self.set(x1, y1, &cell(upper_left));
+        self.set(x2, y1, &cell(upper_right));
+        self.set(x1, y2, &cell(lower_left));
+        self.set(x2, y2, &cell(lower_right));
+    }
+
+    /// Fills the rectangle including its outline
+    pub fn fill_rect(&mut self, rect: &Rect, renderable: &Renderable) {
+        for y in rect.y1..=rect.y2 {
+            self.hline(rect.x1, y, rect.x2 - rect.x1 + 1, renderable);
+        }
+    }
+
+    /// Renders `len` cells to the right starting at (x, y)
+    pub fn hline(&mut self, x: i32, y: i32, len: i32, renderable: &Renderable) {
+        for i in 0..len {
+            self.set(x + i, y, renderable);
+        }
+    }
+
+    /// Renders `len` cells downward starting at (x, y)
+    pub fn vline(&mut self, x: i32, y: i32, len: i32, renderable: &Renderable) {
+        for i in 0..len {
+            self.set(x, y + i, renderable);
+        }
+    }
+
+    pub fn cols(&self) -> u16 {
+        self.cols
+    }
+
+    pub fn rows(&self) -> u16 {
+        (self.buffer_size / self.cols as usize) as u16
+    }
+
+    /// What will be on screen after the next flush
+    pub fn current_buffer(&self) -> &[Renderable] {
+        &self.current_buffer
+    }
+
+    /// Glyphs of the current buffer, one line per row
+    pub fn text(&self) -> String {
+        self.current_buffer
+            .chunks(self.cols as usize)
+            .map(|row| row.iter().map(|cell| cell.glyph).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    pub fn flush<B>(&mut self, backend: &mut B) -> Result<()>
     where
-        W: Write,
+        B: Backend,
     {
         for idx in 0..self.buffer_size {
-            if self.previous_buffer[idx] == self.current_buffer[idx] {
+            if self.previous_buffer[idx].as_ref() == Some(&self.current_buffer[idx]) {
                 continue;
             }
             let pos = self.origin.translate(&self.idx_xy(idx));
             let render = &self.current_buffer[idx];
 
-            match render.bg {
-                None => queue!(w, ResetColor),
-                Some(color) => queue!(w, SetBackgroundColor(color)),
-            }?;
-            queue!(
-                w,
-                cursor::MoveTo(pos.x as u16, pos.y as u16),
-                SetForegroundColor(render.fg),
-                Print(render.glyph)
-            )?;
-            self.previous_buffer[idx] = render.clone();
+            backend.draw_cell(pos.x as u16, pos.y as u16, render)?;
+            self.previous_buffer[idx] = Some(render.clone());
         }
 
-        w.flush()?;
-        Ok(())
+        backend.flush()
+    }
+
+    /// Renders the cell unless it is outside the buffer
+    fn set(&mut self, x: i32, y: i32, renderable: &Renderable) {
+        if 0 <= x && x < self.cols as i32 && 0 <= y && y < self.rows() as i32 {
+            self.render(x, y, renderable);
+        }
     }
 
     fn xy_idx<T: Into<i32>>(&self, x: T, y: T) -> usize {
@@ -66,8 +185,8 @@ impl Renderer {
     }
 
     fn idx_xy(&self, idx: usize) -> Offset {
-        let x = idx as u16 % self.cols;
-        let y = idx as u16 / self.cols;
-        Offset::new(x, y)
+        let x = idx % self.cols as usize;
+        let y = idx / self.cols as usize;
+        Offset::new(x as i32, y as i32)
     }
 }
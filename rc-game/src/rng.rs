@@ -0,0 +1,82 @@
+use crossterm::{ErrorKind, Result};
+use rand::{rngs::StdRng, Rng, RngCore, SeedableRng};
+
+use std::{env, io};
+
+/// Command line flag to override the seed, i.e. `--seed 1234` or `--seed=1234`
+pub const SEED_ARG: &str = "--seed";
+/// Environment variable to override the seed if it isn't passed on the command line
+pub const SEED_ENV: &str = "RC_SEED";
+
+/// Random number generator available as a resource in the `World`.
+/// Map builders and systems should draw from it so that the same seed plus the same inputs
+/// always yields the same game.
+pub struct GameRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl GameRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Seeded from the command line or environment if provided, otherwise randomly.
+    /// Fails if the provided seed isn't an unsigned integer.
+    pub fn from_args_or_entropy() -> Result<Self> {
+        let seed = seed_from_args()?.unwrap_or_else(|| rand::thread_rng().gen());
+        Ok(Self::new(seed))
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> std::result::Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+fn seed_from_args() -> Result<Option<u64>> {
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == SEED_ARG {
+            let seed = args.next().unwrap_or_default();
+            return parse_seed(&seed, SEED_ARG).map(Some);
+        }
+        if let Some(seed) = arg.strip_prefix(SEED_ARG).and_then(|s| s.strip_prefix('=')) {
+            return parse_seed(seed, SEED_ARG).map(Some);
+        }
+    }
+    match env::var(SEED_ENV) {
+        Ok(seed) => parse_seed(&seed, SEED_ENV).map(Some),
+        Err(_) => Ok(None),
+    }
+}
+
+fn parse_seed(seed: &str, source: &str) -> Result<u64> {
+    seed.parse().map_err(|_| {
+        let msg = format!(
+            "Invalid seed '{}' passed via {}, expected an unsigned integer",
+            seed, source
+        );
+        ErrorKind::IoError(io::Error::new(io::ErrorKind::InvalidInput, msg))
+    })
+}
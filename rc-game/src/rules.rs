@@ -0,0 +1,11 @@
+/// Rules that affect how entities move, available as a resource in the `World`
+#[derive(Clone, Debug, Default)]
+pub struct MovementRules {
+    /// Reject diagonal moves between two blocked tiles, i.e. from `a` to `b` here:
+    ///
+    /// ```text
+    /// a#
+    /// #b
+    /// ```
+    pub block_diagonal_squeeze: bool,
+}
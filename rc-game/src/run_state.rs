@@ -0,0 +1,15 @@
+/// Where the game is within a turn, available as a resource in the `World`.
+/// Player input is only processed while `AwaitingInput`, after each player action the
+/// `PlayerTurn` and `MonsterTurn` are run once. Rendering continues every frame regardless.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Default)]
+pub enum RunState {
+    #[default]
+    AwaitingInput,
+    PlayerTurn,
+    MonsterTurn,
+    /// The player died, only quitting is still possible
+    GameOver,
+    /// Defined and handled by the game, i.e. inside `Game::update`.
+    /// The game moves out of it by writing another `RunState` resource.
+    Custom(u32),
+}
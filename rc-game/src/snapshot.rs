@@ -0,0 +1,193 @@
+use crossterm::style::Color;
+
+use std::{env, fmt::Display, fs, path::Path};
+
+use crate::{Renderable, Renderer};
+
+/// Set this environment variable to write snapshots instead of comparing against them
+pub const UPDATE_SNAPSHOTS_ENV: &str = "UPDATE_SNAPSHOTS";
+
+/// Plain text dump of a rendered frame.
+/// Each row is wrapped in `|` so trailing spaces survive editors stripping them.
+#[derive(Debug, PartialEq)]
+pub struct Snapshot {
+    glyphs: Vec<String>,
+    colors: Option<(Vec<String>, Vec<String>)>,
+}
+
+impl Snapshot {
+    /// Captures the glyphs and, if `with_colors`, a foreground and background color layer
+    pub fn from_renderer(renderer: &Renderer, with_colors: bool) -> Self {
+        let cols = renderer.cols() as usize;
+        let rows: Vec<&[Renderable]> = renderer.current_buffer().chunks(cols).collect();
+
+        let layer = |cell: &dyn Fn(&Renderable) -> char| -> Vec<String> {
+            rows.iter()
+                .map(|row| row.iter().map(cell).collect())
+                .collect()
+        };
+
+        let glyphs = layer(&|r| r.glyph);
+        let colors = if with_colors {
+            let fg = layer(&|r| color_code(Some(r.fg)));
+            let bg = layer(&|r| color_code(r.bg));
+            Some((fg, bg))
+        } else {
+            None
+        };
+        Self { glyphs, colors }
+    }
+
+    pub fn parse(text: &str) -> Self {
+        let mut glyphs = vec![];
+        let mut fg = vec![];
+        let mut bg = vec![];
+        let mut section = &mut glyphs;
+        for line in text.lines() {
+            match line {
+                "glyphs:" => section = &mut glyphs,
+                "fg:" => section = &mut fg,
+                "bg:" => section = &mut bg,
+                _ => {
+                    let row = line.strip_prefix('|').unwrap_or(line);
+                    let row = row.strip_suffix('|').unwrap_or(row);
+                    section.push(row.to_string())
+                }
+            }
+        }
+        let colors = if fg.is_empty() && bg.is_empty() {
+            None
+        } else {
+            Some((fg, bg))
+        };
+        Self { glyphs, colors }
+    }
+
+    pub fn glyphs(&self) -> &[String] {
+        &self.glyphs
+    }
+
+    /// Lines of `expected` and `self` that differ, rendered as a diff
+    pub fn diff(&self, expected: &Snapshot) -> Option<String> {
+        let mut out = String::new();
+        diff_section(&mut out, "glyphs", &expected.glyphs, &self.glyphs);
+        match (&expected.colors, &self.colors) {
+            (Some((efg, ebg)), Some((fg, bg))) => {
+                diff_section(&mut out, "fg", efg, fg);
+                diff_section(&mut out, "bg", ebg, bg);
+            }
+            (None, None) => {}
+            (Some(_), None) => out.push_str("expected color layers but got none\n"),
+            (None, Some(_)) => out.push_str("got color layers but expected none\n"),
+        }
+        if out.is_empty() {
+            None
+        } else {
+            Some(out)
+        }
+    }
+}
+
+impl Display for Snapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let section = |f: &mut std::fmt::Formatter<'_>, name: &str, rows: &[String]| {
+            writeln!(f, "{}:", name)?;
+            for row in rows {
+                writeln!(f, "|{}|", row)?;
+            }
+            Ok(())
+        };
+        section(f, "glyphs", &self.glyphs)?;
+        if let Some((fg, bg)) = &self.colors {
+            section(f, "fg", fg)?;
+            section(f, "bg", bg)?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares the snapshot against the `.snap` file at `path` and panics with a diff on mismatch.
+/// The file is only written when `UPDATE_SNAPSHOTS` is set, a missing file fails the assertion.
+pub fn assert_snapshot<P>(path: P, snapshot: &Snapshot)
+where
+    P: AsRef<Path>,
+{
+    let path = path.as_ref();
+    if env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).expect("Could not create snapshot directory");
+        }
+        fs::write(path, snapshot.to_string()).expect("Could not write snapshot");
+        return;
+    }
+    if !path.exists() {
+        panic!(
+            "Snapshot {} does not exist, run with {}=1 to create it",
+            path.display(),
+            UPDATE_SNAPSHOTS_ENV
+        );
+    }
+
+    let text = fs::read_to_string(path).expect("Could not read snapshot");
+    let expected = Snapshot::parse(&text);
+    if let Some(diff) = snapshot.diff(&expected) {
+        panic!(
+            "Snapshot {} does not match, rerun with {}=1 to accept the changes\n{}",
+            path.display(),
+            UPDATE_SNAPSHOTS_ENV,
+            diff
+        );
+    }
+}
+
+fn diff_section(out: &mut String, name: &str, expected: &[String], actual: &[String]) {
+    let nrows = std::cmp::max(expected.len(), actual.len());
+    let mut header = false;
+    for row in 0..nrows {
+        let e = expected.get(row).map(String::as_str);
+        let a = actual.get(row).map(String::as_str);
+        if e == a {
+            continue;
+        }
+        if !header {
+            out.push_str(&format!("{}:\n", name));
+            header = true;
+        }
+        let e = e.unwrap_or("");
+        let a = a.unwrap_or("");
+        let marker: String = e
+            .chars()
+            .chain(std::iter::repeat(' '))
+            .zip(a.chars().chain(std::iter::repeat(' ')))
+            .take(std::cmp::max(e.chars().count(), a.chars().count()))
+            .map(|(e, a)| if e == a { ' ' } else { '^' })
+            .collect();
+        out.push_str(&format!("{:>3} - |{}|\n", row, e));
+        out.push_str(&format!("{:>3} + |{}|\n", row, a));
+        out.push_str(&format!("       {}\n", marker));
+    }
+}
+
+/// Single character per color, upper case for the bright variants
+fn color_code(color: Option<Color>) -> char {
+    match color {
+        None | Some(Color::Reset) => ' ',
+        Some(Color::Black) => 'k',
+        Some(Color::DarkGrey) => 'K',
+        Some(Color::DarkRed) => 'r',
+        Some(Color::Red) => 'R',
+        Some(Color::DarkGreen) => 'g',
+        Some(Color::Green) => 'G',
+        Some(Color::DarkYellow) => 'y',
+        Some(Color::Yellow) => 'Y',
+        Some(Color::DarkBlue) => 'b',
+        Some(Color::Blue) => 'B',
+        Some(Color::DarkMagenta) => 'm',
+        Some(Color::Magenta) => 'M',
+        Some(Color::DarkCyan) => 'c',
+        Some(Color::Cyan) => 'C',
+        Some(Color::Grey) => 'w',
+        Some(Color::White) => 'W',
+        Some(Color::Rgb { .. }) | Some(Color::AnsiValue(_)) => '*',
+    }
+}
@@ -0,0 +1,93 @@
+use specs::{prelude::*, storage::MaskedStorage};
+use std::ops::Deref;
+
+use crate::{Offset, Rect};
+
+/// Entities on each tile of the map, available as a resource in the `World`.
+/// Rebuilt from all entities with a `Position` by the `MapIndexingSystem`.
+#[derive(Default)]
+pub struct SpatialIndex {
+    width: u16,
+    height: u16,
+    tiles: Vec<Vec<Entity>>,
+}
+
+impl SpatialIndex {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            tiles: vec![vec![]; width as usize * height as usize],
+        }
+    }
+
+    /// Removes all entities and resizes the index if the map size changed
+    pub fn reset(&mut self, width: u16, height: u16) {
+        if self.width != width || self.height != height {
+            *self = Self::new(width, height);
+            return;
+        }
+        for tile in self.tiles.iter_mut() {
+            tile.clear();
+        }
+    }
+
+    pub fn insert(&mut self, x: i32, y: i32, entity: Entity) {
+        if let Some(idx) = self.idx(x, y) {
+            self.tiles[idx].push(entity);
+        }
+    }
+
+    pub fn remove(&mut self, x: i32, y: i32, entity: Entity) {
+        if let Some(idx) = self.idx(x, y) {
+            self.tiles[idx].retain(|e| *e != entity);
+        }
+    }
+
+    /// Keeps the index up to date when an entity moves within a turn
+    pub fn move_entity(&mut self, entity: Entity, from: Offset, to: Offset) {
+        self.remove(from.x, from.y, entity);
+        self.insert(to.x, to.y, entity);
+    }
+
+    pub fn entities_at(&self, x: i32, y: i32) -> &[Entity] {
+        match self.idx(x, y) {
+            Some(idx) => &self.tiles[idx],
+            None => &[],
+        }
+    }
+
+    /// First entity on the tile that has a component in the given storage, i.e.
+    /// `index.first_with(x, y, &monsters)`
+    pub fn first_with<C, D>(&self, x: i32, y: i32, storage: &Storage<C, D>) -> Option<Entity>
+    where
+        C: Component,
+        D: Deref<Target = MaskedStorage<C>>,
+    {
+        self.entities_at(x, y)
+            .iter()
+            .find(|entity| storage.contains(**entity))
+            .copied()
+    }
+
+    /// All entities inside the rectangle along with their tile
+    pub fn in_rect(&self, rect: &Rect) -> Vec<(Offset, Entity)> {
+        let mut found = vec![];
+        for y in rect.y1..=rect.y2 {
+            for x in rect.x1..=rect.x2 {
+                for entity in self.entities_at(x, y) {
+                    found.push((Offset::new(x, y), *entity));
+                }
+            }
+        }
+        found
+    }
+
+    fn idx(&self, x: i32, y: i32) -> Option<usize> {
+        if 0 <= x && x < self.width as i32 && 0 <= y && y < self.height as i32 {
+            Some(y as usize * self.width as usize + x as usize)
+        } else {
+            None
+        }
+    }
+}
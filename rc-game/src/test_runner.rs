@@ -0,0 +1,128 @@
+use crossterm::{event::Event, Result};
+
+use std::collections::BTreeMap;
+
+use crate::{Game, MemoryBackend, RogueCrossGame, Snapshot, MS_PER_FRAME};
+
+/// Drives a game headless and frame by frame, feeding it scripted events.
+/// Frames are stepped as fast as possible, the framerate is never enforced.
+pub struct TestRunner<TGame>
+where
+    TGame: Game,
+{
+    game: RogueCrossGame<TGame, MemoryBackend>,
+    script: BTreeMap<u64, Vec<Event>>,
+    frame: u64,
+    exited: bool,
+}
+
+impl<TGame> TestRunner<TGame>
+where
+    TGame: Game,
+{
+    /// Initializes the game, so build the map and set the player start beforehand
+    pub fn new(mut game: RogueCrossGame<TGame, MemoryBackend>) -> Result<Self> {
+        game.init()?;
+        Ok(Self {
+            game,
+            script: BTreeMap::new(),
+            frame: 0,
+            exited: false,
+        })
+    }
+
+    /// Queues the events to be received one per frame starting with the next frame
+    pub fn script<I>(&mut self, events: I) -> &mut Self
+    where
+        I: IntoIterator<Item = Event>,
+    {
+        let start = self
+            .script
+            .keys()
+            .next_back()
+            .map_or(self.frame, |last| std::cmp::max(*last + 1, self.frame));
+        for (i, event) in events.into_iter().enumerate() {
+            self.at_frame(start + i as u64, event);
+        }
+        self
+    }
+
+    /// Queues the event to be received at the given frame.
+    /// When multiple events are queued for the same frame they spill into the following ones.
+    pub fn at_frame(&mut self, frame: u64, event: Event) -> &mut Self {
+        self.script.entry(frame).or_default().push(event);
+        self
+    }
+
+    /// Queues the event to be received at the frame that would run after `millis` in real time
+    pub fn at_millis(&mut self, millis: u64, event: Event) -> &mut Self {
+        self.at_frame(millis / MS_PER_FRAME, event)
+    }
+
+    /// Polls, updates and renders a single frame.
+    /// Returns `false` once the game exited, further steps are ignored.
+    pub fn step(&mut self) -> Result<bool> {
+        if self.exited {
+            return Ok(false);
+        }
+        if let Some(events) = self.script.remove(&self.frame) {
+            self.game.backend.push_events(events);
+        }
+        self.frame += 1;
+        if !self.game.step()? {
+            self.exited = true;
+            self.game.deinit()?;
+        }
+        Ok(!self.exited)
+    }
+
+    pub fn step_n(&mut self, frames: u64) -> Result<bool> {
+        for _ in 0..frames {
+            if !self.step()? {
+                break;
+            }
+        }
+        Ok(!self.exited)
+    }
+
+    /// Steps until all scripted events were consumed, returns `false` if the game exited
+    pub fn run_script(&mut self) -> Result<bool> {
+        while !self.exited && (!self.script.is_empty() || self.game.backend.pending_events() > 0) {
+            self.step()?;
+        }
+        Ok(!self.exited)
+    }
+
+    /// Number of frames stepped so far
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn has_exited(&self) -> bool {
+        self.exited
+    }
+
+    /// Glyphs the renderer holds for the game area, one line per row
+    pub fn buffer_text(&self) -> String {
+        self.game
+            .renderer()
+            .expect("Game should be initialized")
+            .text()
+    }
+
+    /// Snapshot of what the renderer holds for the game area
+    pub fn snapshot(&self, with_colors: bool) -> Snapshot {
+        Snapshot::from_renderer(
+            self.game.renderer().expect("Game should be initialized"),
+            with_colors,
+        )
+    }
+
+    pub fn game(&self) -> &RogueCrossGame<TGame, MemoryBackend> {
+        &self.game
+    }
+
+    pub fn game_mut(&mut self) -> &mut RogueCrossGame<TGame, MemoryBackend> {
+        &mut self.game
+    }
+}
@@ -0,0 +1,36 @@
+use specs::prelude::*;
+
+use crate::{field_of_view, Map, Offset, Player, Position, Viewshed};
+
+/// Recomputes dirty viewsheds and updates which map tiles the player sees and has seen
+pub struct VisibilitySystem {}
+
+impl<'a> System<'a> for VisibilitySystem {
+    type SystemData = (
+        WriteExpect<'a, Map>,
+        WriteStorage<'a, Viewshed>,
+        ReadStorage<'a, Position>,
+        ReadStorage<'a, Player>,
+    );
+
+    fn run(&mut self, (mut map, mut viewsheds, positions, players): Self::SystemData) {
+        for (viewshed, pos, player) in (&mut viewsheds, &positions, players.maybe()).join() {
+            if !viewshed.dirty {
+                continue;
+            }
+            viewshed.dirty = false;
+            viewshed.visible_tiles = field_of_view(Offset::from(pos), viewshed.range, &map);
+
+            if player.is_some() {
+                for visible in map.visible_tiles.iter_mut() {
+                    *visible = false;
+                }
+                for tile in viewshed.visible_tiles.iter() {
+                    let idx = map.xy_idx(tile.x, tile.y);
+                    map.revealed_tiles[idx] = true;
+                    map.visible_tiles[idx] = true;
+                }
+            }
+        }
+    }
+}
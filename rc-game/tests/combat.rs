@@ -0,0 +1,134 @@
+use crossterm::event::KeyCode;
+use rc_game::*;
+use specs::prelude::*;
+
+mod common;
+
+use common::{key, log_texts, player_position, position_of, walled_map};
+
+#[derive(Default)]
+struct CombatGame {}
+
+impl Game for CombatGame {
+    fn build_player<'a>(&self, player: EntityBuilder<'a>) -> EntityBuilder<'a> {
+        player.with(CombatStats::new(30, 2, 5))
+    }
+}
+
+fn spawn_orc(ecs: &mut World, x: i32, y: i32, stats: CombatStats) -> EntityBuilder<'_> {
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Collider {})
+        .with(Monster {})
+        .with(Name::new("Orc"))
+        .with(stats)
+        .with(Renderable {
+            glyph: 'o',
+            ..Default::default()
+        })
+}
+
+fn runner_with<F>(configure: F) -> TestRunner<CombatGame>
+where
+    F: FnOnce(&mut RogueCrossGame<CombatGame, MemoryBackend>),
+{
+    let mut game: RogueCrossGame<CombatGame, MemoryBackend> = Default::default();
+    game.set_seed(1);
+    game.build_map(walled_map);
+    configure(&mut game);
+    TestRunner::new(game).unwrap()
+}
+
+fn hp_of<G: Game>(runner: &TestRunner<G>, name: &str) -> Option<i32> {
+    let ecs = runner.game().ecs();
+    let names = ecs.read_storage::<Name>();
+    let stats = ecs.read_storage::<CombatStats>();
+    (&names, &stats)
+        .join()
+        .find(|(n, _)| n.name == name)
+        .map(|(_, stats)| stats.hp)
+}
+
+fn player_hp<G: Game>(runner: &TestRunner<G>) -> i32 {
+    let ecs = runner.game().ecs();
+    let players = ecs.read_storage::<Player>();
+    let stats = ecs.read_storage::<CombatStats>();
+    (&players, &stats).join().next().unwrap().1.hp
+}
+
+fn run_state<G: Game>(runner: &TestRunner<G>) -> RunState {
+    *runner.game().ecs().fetch::<RunState>()
+}
+
+#[test]
+fn bumping_a_monster_attacks_it() {
+    let mut runner = runner_with(|game| {
+        spawn_orc(game.ecs_mut(), 10, 5, CombatStats::new(10, 1, 4)).build();
+    });
+    runner.script(vec![key(KeyCode::Left), key(KeyCode::Left)]);
+    runner.run_script().unwrap();
+
+    assert_eq!(hp_of(&runner, "Orc"), Some(6));
+    assert_eq!(player_position(&runner), Offset::new(11, 5));
+    assert!(log_texts(&runner).contains(&"Player hits Orc for 4 hp.".to_string()));
+}
+
+#[test]
+fn monsters_without_hit_points_die() {
+    let mut runner = runner_with(|game| {
+        spawn_orc(game.ecs_mut(), 10, 5, CombatStats::new(4, 1, 4)).build();
+    });
+    runner.script(vec![key(KeyCode::Left), key(KeyCode::Left)]);
+    runner.run_script().unwrap();
+
+    assert_eq!(position_of(&runner, "Orc"), None);
+    assert!(log_texts(&runner).contains(&"Orc dies.".to_string()));
+
+    // The tile is free again
+    runner.script(vec![key(KeyCode::Left)]);
+    runner.run_script().unwrap();
+    assert_eq!(player_position(&runner), Offset::new(10, 5));
+}
+
+#[test]
+fn a_dead_player_ends_the_game() {
+    let mut runner = runner_with(|game| {
+        spawn_orc(game.ecs_mut(), 11, 5, CombatStats::new(10, 1, 40))
+            .with(Viewshed::new(8))
+            .build();
+    });
+    runner.script(vec![key(KeyCode::Char('.'))]);
+    runner.run_script().unwrap();
+
+    assert!(player_hp(&runner) <= 0);
+    assert_eq!(run_state(&runner), RunState::GameOver);
+    assert_eq!(log_texts(&runner).last().unwrap(), "You die.");
+
+    runner.script(vec![key(KeyCode::Right), key(KeyCode::Char('.'))]);
+    runner.run_script().unwrap();
+    assert_eq!(player_position(&runner), Offset::new(12, 5));
+    assert_eq!(run_state(&runner), RunState::GameOver);
+    assert!(!runner.has_exited());
+}
+
+#[test]
+fn no_attacks_through_a_diagonal_squeeze() {
+    let mut runner = runner_with(|game| {
+        game.set_movement_rules(MovementRules {
+            block_diagonal_squeeze: true,
+        });
+        let mut map = game.ecs().fetch_mut::<Map>();
+        map.set_tile(11, 5, TileType::Wall);
+        map.set_tile(12, 4, TileType::Wall);
+        drop(map);
+        spawn_orc(game.ecs_mut(), 11, 4, CombatStats::new(10, 1, 4))
+            .with(Viewshed::new(8))
+            .build();
+    });
+    runner.script(vec![key(KeyCode::Home), key(KeyCode::Char('.'))]);
+    runner.run_script().unwrap();
+
+    assert_eq!(hp_of(&runner, "Orc"), Some(10));
+    assert_eq!(player_hp(&runner), 30);
+    assert_eq!(player_position(&runner), Offset::new(12, 5));
+}
@@ -0,0 +1,58 @@
+#![allow(dead_code)]
+
+use crossterm::event::{Event, KeyCode};
+use rc_game::*;
+use specs::prelude::*;
+
+/// Floor surrounded by walls with a wall splitting it at x = 20
+pub fn walled_map(gs: &GameState, player_position: &mut Offset, _rng: &mut GameRng) -> Map {
+    let mut map = Map::filled(gs.cols, gs.rows, TileType::Floor);
+    for x in 0..gs.cols as i32 {
+        map.set_tile(x, 0, TileType::Wall);
+        map.set_tile(x, gs.rows as i32 - 1, TileType::Wall);
+    }
+    for y in 0..gs.rows as i32 {
+        map.set_tile(0, y, TileType::Wall);
+        map.set_tile(gs.cols as i32 - 1, y, TileType::Wall);
+        map.set_tile(20, y, TileType::Wall);
+    }
+    *player_position = Offset::new(12, 5);
+    map
+}
+
+pub fn key(code: KeyCode) -> Event {
+    Event::Key(code.into())
+}
+
+pub fn row<G: Game>(runner: &TestRunner<G>, y: usize) -> String {
+    runner.buffer_text().lines().nth(y).unwrap().to_string()
+}
+
+/// Column of the glyph in the row or `None` if it isn't there
+pub fn col_of<G: Game>(runner: &TestRunner<G>, y: usize, glyph: char) -> Option<usize> {
+    row(runner, y).chars().position(|c| c == glyph)
+}
+
+/// Position of the entity with the `Name` or `None` if it doesn't exist (anymore)
+pub fn position_of<G: Game>(runner: &TestRunner<G>, name: &str) -> Option<Offset> {
+    let ecs = runner.game().ecs();
+    let names = ecs.read_storage::<Name>();
+    let positions = ecs.read_storage::<Position>();
+    (&names, &positions)
+        .join()
+        .find(|(n, _)| n.name == name)
+        .map(|(_, pos)| Offset::from(pos))
+}
+
+pub fn player_position<G: Game>(runner: &TestRunner<G>) -> Offset {
+    let ecs = runner.game().ecs();
+    let players = ecs.read_storage::<Player>();
+    let positions = ecs.read_storage::<Position>();
+    let (_, pos) = (&players, &positions).join().next().unwrap();
+    Offset::from(pos)
+}
+
+pub fn log_texts<G: Game>(runner: &TestRunner<G>) -> Vec<String> {
+    let log = runner.game().ecs().fetch::<GameLog>();
+    log.entries().iter().map(|e| e.text.clone()).collect()
+}
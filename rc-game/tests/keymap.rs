@@ -0,0 +1,50 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use rc_game::*;
+
+#[test]
+fn default_keymap_keeps_arrow_diagonals_and_scrolls_the_log() {
+    let keymap = KeyMap::default();
+    let action = |code, modifiers| keymap.action_for(&KeyEvent::new(code, modifiers));
+
+    assert_eq!(
+        action(KeyCode::PageUp, KeyModifiers::NONE),
+        Some(Action::Move(Direction::NorthEast))
+    );
+    assert_eq!(
+        action(KeyCode::Home, KeyModifiers::NONE),
+        Some(Action::Move(Direction::NorthWest))
+    );
+    assert_eq!(
+        action(KeyCode::PageUp, KeyModifiers::SHIFT),
+        Some(Action::ScrollLogUp)
+    );
+    assert_eq!(
+        action(KeyCode::PageDown, KeyModifiers::SHIFT),
+        Some(Action::ScrollLogDown)
+    );
+    assert_eq!(
+        action(KeyCode::Char('['), KeyModifiers::NONE),
+        Some(Action::ScrollLogUp)
+    );
+    assert_eq!(action(KeyCode::Char('9'), KeyModifiers::NONE), None);
+}
+
+#[test]
+fn config_overrides_bindings() {
+    let mut keymap = KeyMap::default();
+    keymap
+        .apply_config("preset = vi\nshift+pageup = none\nctrl+q = quit # comment\n")
+        .unwrap();
+    let action = |code, modifiers| keymap.action_for(&KeyEvent::new(code, modifiers));
+
+    assert_eq!(
+        action(KeyCode::Char('y'), KeyModifiers::NONE),
+        Some(Action::Move(Direction::NorthWest))
+    );
+    assert_eq!(action(KeyCode::PageUp, KeyModifiers::SHIFT), None);
+    assert_eq!(
+        action(KeyCode::Char('q'), KeyModifiers::CONTROL),
+        Some(Action::Quit)
+    );
+    assert!(keymap.apply_config("x = fly").is_err());
+}
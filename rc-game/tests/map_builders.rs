@@ -0,0 +1,41 @@
+use rc_game::*;
+
+#[test]
+fn rooms_and_corridors_keep_the_border_solid() {
+    for seed in 0..200 {
+        let map = RoomsAndCorridors::new(GAME_COLS, GAME_ROWS).build(&mut GameRng::new(seed));
+        let (width, height) = (map.width as i32, map.height as i32);
+        for x in 0..width {
+            for y in [0, height - 1].iter() {
+                assert_eq!(
+                    map.tile(x, *y),
+                    Some(TileType::Wall),
+                    "seed {} at ({}, {})",
+                    seed,
+                    x,
+                    y
+                );
+            }
+        }
+        for y in 0..height {
+            for x in [0, width - 1].iter() {
+                assert_eq!(
+                    map.tile(*x, y),
+                    Some(TileType::Wall),
+                    "seed {} at ({}, {})",
+                    seed,
+                    x,
+                    y
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn rooms_and_corridors_carve_rooms() {
+    let map = RoomsAndCorridors::new(GAME_COLS, GAME_ROWS).build(&mut GameRng::new(1));
+    assert!(!map.rooms.is_empty());
+    let center = map.rooms[0].center();
+    assert_eq!(map.tile(center.x, center.y), Some(TileType::Floor));
+}
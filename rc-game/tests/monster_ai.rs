@@ -0,0 +1,57 @@
+use crossterm::event::KeyCode;
+use rc_game::*;
+use specs::prelude::*;
+
+mod common;
+
+use common::{key, position_of, walled_map};
+
+#[derive(Default)]
+struct ChaseGame {}
+
+impl Game for ChaseGame {}
+
+/// Runner with a monster that sees up to 8 tiles at the position
+fn runner_with_monster(x: i32, y: i32) -> TestRunner<ChaseGame> {
+    let mut game: RogueCrossGame<ChaseGame, MemoryBackend> = Default::default();
+    game.build_map(walled_map);
+    game.ecs_mut()
+        .create_entity()
+        .with(Position { x, y })
+        .with(Collider {})
+        .with(Monster {})
+        .with(Name::new("Goblin"))
+        .with(Viewshed::new(8))
+        .with(Renderable {
+            glyph: 'g',
+            ..Default::default()
+        })
+        .build();
+    TestRunner::new(game).unwrap()
+}
+
+#[test]
+fn monsters_that_see_the_player_step_towards_them_each_turn() {
+    let mut runner = runner_with_monster(16, 5);
+    runner.step_n(5).unwrap();
+    assert_eq!(position_of(&runner, "Goblin"), Some(Offset::new(16, 5)));
+
+    runner.script(vec![key(KeyCode::Char('.'))]);
+    runner.run_script().unwrap();
+    assert_eq!(position_of(&runner, "Goblin"), Some(Offset::new(15, 5)));
+
+    runner.script(vec![key(KeyCode::Char('.'))]);
+    runner.run_script().unwrap();
+    assert_eq!(position_of(&runner, "Goblin"), Some(Offset::new(14, 5)));
+    runner.step_n(5).unwrap();
+    assert_eq!(position_of(&runner, "Goblin"), Some(Offset::new(14, 5)));
+}
+
+#[test]
+fn monsters_stay_put_while_the_player_is_out_of_sight() {
+    // Behind the wall at x = 20
+    let mut runner = runner_with_monster(24, 5);
+    runner.script(vec![key(KeyCode::Char('.')); 3]);
+    runner.run_script().unwrap();
+    assert_eq!(position_of(&runner, "Goblin"), Some(Offset::new(24, 5)));
+}
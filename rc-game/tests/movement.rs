@@ -0,0 +1,52 @@
+use rc_game::*;
+use specs::prelude::*;
+
+fn world_with_entities(count: usize) -> (World, Vec<Entity>) {
+    let mut world = World::new();
+    let entities = (0..count).map(|_| world.create_entity().build()).collect();
+    (world, entities)
+}
+
+#[test]
+fn move_by_updates_map_occupants_and_spatial_index() {
+    let (_world, entities) = world_with_entities(1);
+    let entity = entities[0];
+    let mut map = Map::filled(10, 10, TileType::Floor);
+    let mut index = SpatialIndex::new(10, 10);
+    let mut pos = Position { x: 2, y: 2 };
+    map.set_occupant(2, 2, entity);
+    index.insert(2, 2, entity);
+
+    let rules = MovementRules::default();
+    let outcome = move_by(&mut map, &mut index, &rules, entity, &mut pos, 1, 1);
+
+    assert_eq!(outcome, MoveOutcome::Moved);
+    assert_eq!((pos.x, pos.y), (3, 3));
+    assert_eq!(map.occupant(2, 2), None);
+    assert_eq!(map.occupant(3, 3), Some(entity));
+    assert!(index.entities_at(2, 2).is_empty());
+    assert_eq!(index.entities_at(3, 3), &[entity]);
+}
+
+#[test]
+fn move_by_reports_bumps_and_blocks() {
+    let (_world, entities) = world_with_entities(3);
+    let (entity, other, squeezed) = (entities[0], entities[1], entities[2]);
+    let mut map = Map::filled(10, 10, TileType::Floor);
+    map.set_tile(1, 2, TileType::Wall);
+    map.set_tile(2, 1, TileType::Wall);
+    map.set_occupant(3, 2, other);
+    map.set_occupant(1, 1, squeezed);
+    let mut index = SpatialIndex::new(10, 10);
+    let mut pos = Position { x: 2, y: 2 };
+    let rules = MovementRules {
+        block_diagonal_squeeze: true,
+    };
+
+    let mut move_to = |dx, dy| move_by(&mut map, &mut index, &rules, entity, &mut pos, dx, dy);
+    assert_eq!(move_to(-1, 0), MoveOutcome::Blocked);
+    assert_eq!(move_to(1, 0), MoveOutcome::Bumped(other));
+    // Squeezing between two walls is blocked even onto an occupied tile, so it isn't an attack
+    assert_eq!(move_to(-1, -1), MoveOutcome::Blocked);
+    assert_eq!((pos.x, pos.y), (2, 2));
+}
@@ -0,0 +1,116 @@
+use rc_game::*;
+
+fn floor(width: u16, height: u16) -> Map {
+    Map::filled(width, height, TileType::Floor)
+}
+
+/// 5x5 floor where stepping from (2, 2) to (1, 1) squeezes between two walls
+fn squeeze_map() -> Map {
+    let mut map = floor(5, 5);
+    map.set_tile(1, 2, TileType::Wall);
+    map.set_tile(2, 1, TileType::Wall);
+    map
+}
+
+fn squeeze_options() -> PathOptions {
+    PathOptions {
+        block_diagonal_squeeze: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn a_star_prefers_straight_paths() {
+    let map = floor(10, 5);
+    let path = a_star(&map, Offset::new(1, 2), Offset::new(5, 2)).unwrap();
+    let expected: Vec<Offset> = (2..=5).map(|x| Offset::new(x, 2)).collect();
+    assert_eq!(path, expected);
+}
+
+#[test]
+fn a_star_walks_around_walls() {
+    let mut map = floor(10, 5);
+    for y in 0..4 {
+        map.set_tile(5, y, TileType::Wall);
+    }
+    let path = a_star(&map, Offset::new(0, 0), Offset::new(9, 0)).unwrap();
+    assert_eq!(path.last(), Some(&Offset::new(9, 0)));
+    assert!(path.contains(&Offset::new(5, 4)));
+    assert!(path.iter().all(|p| !map.is_terrain_blocked(p.x, p.y)));
+    assert_eq!(path.len(), 9);
+
+    map.set_tile(5, 4, TileType::Wall);
+    assert_eq!(a_star(&map, Offset::new(0, 0), Offset::new(9, 0)), None);
+}
+
+#[test]
+fn a_star_with_four_way_movement() {
+    let map = floor(10, 10);
+    let options = PathOptions {
+        connectivity: Connectivity::Four,
+        ..Default::default()
+    };
+    let path = a_star_with(&map, Offset::new(0, 0), Offset::new(3, 3), &options).unwrap();
+    assert_eq!(path.len(), 6);
+    let eight = a_star(&map, Offset::new(0, 0), Offset::new(3, 3)).unwrap();
+    assert_eq!(eight.len(), 3);
+}
+
+#[test]
+fn a_star_avoids_diagonal_squeezes() {
+    let map = squeeze_map();
+    let path = a_star_with(
+        &map,
+        Offset::new(2, 2),
+        Offset::new(1, 1),
+        &squeeze_options(),
+    )
+    .unwrap();
+    assert_ne!(path[0], Offset::new(1, 1));
+    assert_eq!(path.last(), Some(&Offset::new(1, 1)));
+}
+
+#[test]
+fn dijkstra_map_uses_the_a_star_costs() {
+    let map = floor(10, 10);
+    let sources = [Offset::new(0, 0), Offset::new(9, 9)];
+    let dijkstra = DijkstraMap::new(&map, &sources, &PathOptions::default());
+    assert_eq!(dijkstra.value(0, 0), Some(0));
+    assert_eq!(dijkstra.value(3, 0), Some(6));
+    assert_eq!(dijkstra.value(3, 3), Some(9));
+    assert_eq!(dijkstra.value(8, 9), Some(2));
+    assert_eq!(dijkstra.value(10, 0), None);
+
+    let capped = DijkstraMap::with_max_cost(&map, &sources, &PathOptions::default(), 4);
+    assert_eq!(capped.value(2, 0), Some(4));
+    assert_eq!(capped.value(3, 0), None);
+}
+
+#[test]
+fn dijkstra_map_steps_towards_the_closest_source() {
+    let map = floor(10, 10);
+    let options = PathOptions::default();
+    let dijkstra = DijkstraMap::new(&map, &[Offset::new(0, 0)], &options);
+    assert_eq!(
+        dijkstra.step_towards(&map, Offset::new(3, 0), &options),
+        Some(Offset::new(2, 0))
+    );
+    assert_eq!(
+        dijkstra.step_towards(&map, Offset::new(3, 3), &options),
+        Some(Offset::new(2, 2))
+    );
+    assert_eq!(
+        dijkstra.step_towards(&map, Offset::new(0, 0), &options),
+        None
+    );
+}
+
+#[test]
+fn dijkstra_map_avoids_diagonal_squeezes() {
+    let map = squeeze_map();
+    let options = squeeze_options();
+    let dijkstra = DijkstraMap::new(&map, &[Offset::new(1, 1)], &options);
+    let step = dijkstra.step_towards(&map, Offset::new(2, 2), &options);
+    assert!(step.is_some());
+    assert_ne!(step, Some(Offset::new(1, 1)));
+}
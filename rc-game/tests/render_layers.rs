@@ -0,0 +1,69 @@
+use crossterm::style::Color;
+use rc_game::*;
+use specs::prelude::*;
+
+mod common;
+
+use common::walled_map;
+
+#[derive(Default)]
+struct LayersGame {}
+
+impl Game for LayersGame {}
+
+fn spawn(ecs: &mut World, x: i32, y: i32, glyph: char, bg: Option<Color>, layer: RenderLayer) {
+    ecs.create_entity()
+        .with(Position { x, y })
+        .with(Renderable {
+            glyph,
+            fg: Color::White,
+            bg,
+        })
+        .with(layer)
+        .build();
+}
+
+fn cell(runner: &TestRunner<LayersGame>, x: usize, y: usize) -> Renderable {
+    let renderer = runner.game().renderer().unwrap();
+    renderer.current_buffer()[y * renderer.cols() as usize + x].clone()
+}
+
+fn runner_with<F>(spawn_entities: F) -> TestRunner<LayersGame>
+where
+    F: FnOnce(&mut World),
+{
+    let mut game: RogueCrossGame<LayersGame, MemoryBackend> = Default::default();
+    game.build_map(walled_map);
+    spawn_entities(game.ecs_mut());
+    let mut runner = TestRunner::new(game).unwrap();
+    runner.step().unwrap();
+    runner
+}
+
+#[test]
+fn higher_layers_are_drawn_on_top_regardless_of_spawn_order() {
+    let runner = runner_with(|ecs| {
+        spawn(ecs, 14, 5, 'o', None, RenderLayer::Actors);
+        spawn(ecs, 14, 5, '!', None, RenderLayer::Items);
+        spawn(ecs, 15, 5, '*', None, RenderLayer::Effects);
+        spawn(ecs, 15, 5, 'o', None, RenderLayer::Actors);
+    });
+    assert_eq!(cell(&runner, 14, 5).glyph, 'o');
+    assert_eq!(cell(&runner, 15, 5).glyph, '*');
+}
+
+#[test]
+fn renderables_without_a_background_keep_the_one_beneath() {
+    let runner = runner_with(|ecs| {
+        spawn(ecs, 14, 5, 'o', None, RenderLayer::Actors);
+        spawn(ecs, 14, 5, '~', Some(Color::Blue), RenderLayer::Terrain);
+        spawn(ecs, 15, 5, 'o', Some(Color::Red), RenderLayer::Actors);
+        spawn(ecs, 15, 5, '!', Some(Color::Green), RenderLayer::Items);
+    });
+    let kept = cell(&runner, 14, 5);
+    assert_eq!((kept.glyph, kept.bg), ('o', Some(Color::Blue)));
+    let replaced = cell(&runner, 15, 5);
+    assert_eq!((replaced.glyph, replaced.bg), ('o', Some(Color::Red)));
+    // Plain terrain has no background
+    assert_eq!(cell(&runner, 16, 5).bg, None);
+}
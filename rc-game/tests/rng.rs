@@ -0,0 +1,25 @@
+use rc_game::*;
+
+#[derive(Default)]
+struct SeededGame {}
+
+impl Game for SeededGame {}
+
+// A single test since it changes the environment of the whole process
+#[test]
+fn malformed_seeds_are_errors() {
+    std::env::set_var(SEED_ENV, "1234");
+    assert_eq!(GameRng::from_args_or_entropy().unwrap().seed(), 1234);
+
+    std::env::set_var(SEED_ENV, "abc");
+    let err = GameRng::from_args_or_entropy().err().unwrap();
+    assert!(format!("{:?}", err).contains("Invalid seed 'abc' passed via RC_SEED"));
+
+    let game: RogueCrossGame<SeededGame, MemoryBackend> = Default::default();
+    assert!(TestRunner::new(game).is_err());
+
+    let mut game: RogueCrossGame<SeededGame, MemoryBackend> = Default::default();
+    game.set_seed(7);
+    assert!(TestRunner::new(game).is_ok());
+    std::env::remove_var(SEED_ENV);
+}
@@ -0,0 +1,56 @@
+use crossterm::event::{Event, KeyCode};
+use rc_game::*;
+
+#[derive(Default)]
+struct SnapshotGame {}
+
+impl Game for SnapshotGame {}
+
+fn snapshot_path(name: &str) -> String {
+    format!(
+        "{}/tests/snapshots/{}.snap",
+        env!("CARGO_MANIFEST_DIR"),
+        name
+    )
+}
+
+fn rooms_and_corridors_runner() -> TestRunner<SnapshotGame> {
+    let mut game: RogueCrossGame<SnapshotGame, MemoryBackend> = Default::default();
+    game.set_seed(7);
+    game.build_map(rooms_and_corridors);
+    game.set_player_viewshed(8);
+    TestRunner::new(game).unwrap()
+}
+
+#[test]
+fn rooms_and_corridors_with_seed() {
+    let mut runner = rooms_and_corridors_runner();
+    runner.step().unwrap();
+    assert_snapshot(
+        snapshot_path("rooms_and_corridors_seed_7"),
+        &runner.snapshot(true),
+    );
+}
+
+#[test]
+fn rooms_and_corridors_after_walking() {
+    let mut runner = rooms_and_corridors_runner();
+    runner.script(vec![Event::Key(KeyCode::Right.into()); 3]);
+    runner.run_script().unwrap();
+    runner.step().unwrap();
+    assert_snapshot(
+        snapshot_path("rooms_and_corridors_seed_7_walked"),
+        &runner.snapshot(false),
+    );
+}
+
+#[test]
+#[should_panic(expected = "does not exist")]
+fn missing_snapshots_fail() {
+    if std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some() {
+        panic!("Snapshot does not exist, skipped while updating snapshots");
+    }
+    let mut runner = rooms_and_corridors_runner();
+    runner.step().unwrap();
+    assert_snapshot(snapshot_path("missing"), &runner.snapshot(false));
+}
@@ -0,0 +1,225 @@
+use crossterm::{
+    event::{Event, KeyCode},
+    style::Color,
+    Result,
+};
+use rc_game::*;
+use specs::prelude::*;
+
+mod common;
+
+use common::{col_of, key, row, walled_map};
+
+#[derive(Default)]
+struct TestGame {
+    bumps: u32,
+}
+
+impl Game for TestGame {
+    fn spawn_map_entities(&self, _gs: &GameState, ecs: &mut World) -> Result<()> {
+        ecs.create_entity()
+            .with(Position { x: 10, y: 5 })
+            .with(Collider {})
+            .with(Renderable {
+                glyph: 'o',
+                ..Default::default()
+            })
+            .build();
+        Ok(())
+    }
+
+    fn bump(
+        &mut self,
+        _player: Entity,
+        _target: Entity,
+        _gs: &GameState,
+        _ecs: &World,
+    ) -> Result<ActionOutcome> {
+        self.bumps += 1;
+        Ok(ActionOutcome::TookTurn)
+    }
+}
+
+fn runner_with<F>(configure: F) -> TestRunner<TestGame>
+where
+    F: FnOnce(&mut RogueCrossGame<TestGame, MemoryBackend>),
+{
+    let mut game: RogueCrossGame<TestGame, MemoryBackend> = Default::default();
+    game.set_seed(1);
+    game.build_map(walled_map);
+    configure(&mut game);
+    TestRunner::new(game).unwrap()
+}
+
+fn runner() -> TestRunner<TestGame> {
+    runner_with(|_| {})
+}
+
+#[test]
+fn moves_the_player_with_the_arrow_keys() {
+    let mut runner = runner();
+    runner.step().unwrap();
+    assert_eq!(col_of(&runner, 5, '@'), Some(12));
+
+    runner.script(vec![key(KeyCode::Right), key(KeyCode::Down)]);
+    runner.run_script().unwrap();
+    runner.step().unwrap();
+    assert_eq!(col_of(&runner, 5, '@'), None);
+    assert_eq!(col_of(&runner, 6, '@'), Some(13));
+}
+
+#[test]
+fn moves_the_player_diagonally() {
+    let mut runner = runner_with(|game| game.set_keymap(KeyMap::with_presets(&[KeyPreset::Vi])));
+    runner.script(vec![key(KeyCode::Char('u'))]);
+    runner.run_script().unwrap();
+    runner.step().unwrap();
+    assert_eq!(col_of(&runner, 4, '@'), Some(13));
+}
+
+#[test]
+fn walls_block_the_player() {
+    let mut runner = runner();
+    runner.script(vec![key(KeyCode::Up); 6]);
+    runner.run_script().unwrap();
+    runner.step().unwrap();
+    assert_eq!(col_of(&runner, 1, '@'), Some(12));
+}
+
+#[test]
+fn bumping_a_collider_calls_the_game_instead_of_moving() {
+    let mut runner = runner();
+    runner.script(vec![key(KeyCode::Left), key(KeyCode::Left)]);
+    runner.run_script().unwrap();
+    runner.step().unwrap();
+
+    assert_eq!(runner.game().game().bumps, 1);
+    assert_eq!(col_of(&runner, 5, '@'), Some(11));
+    assert_eq!(col_of(&runner, 5, 'o'), Some(10));
+}
+
+#[test]
+fn only_renders_what_the_player_sees_or_has_seen() {
+    let mut runner = runner_with(|game| game.set_player_viewshed(4));
+    runner.step().unwrap();
+    // Beyond the wall and out of range
+    assert_eq!(row(&runner, 5).chars().nth(30), Some(' '));
+    assert_eq!(row(&runner, 15).chars().nth(12), Some(' '));
+    assert_eq!(row(&runner, 5).chars().nth(14), Some('.'));
+
+    runner.script(vec![key(KeyCode::Down); 6]);
+    runner.run_script().unwrap();
+    runner.step().unwrap();
+    // Now in range, the start is remembered
+    assert_eq!(row(&runner, 15).chars().nth(12), Some('.'));
+    assert_eq!(row(&runner, 5).chars().nth(12), Some('.'));
+    assert_eq!(row(&runner, 5).chars().nth(30), Some(' '));
+}
+
+#[test]
+fn quits_on_esc() {
+    let mut runner = runner();
+    runner.script(vec![key(KeyCode::Esc)]);
+    assert!(!runner.run_script().unwrap());
+    assert!(runner.has_exited());
+    assert!(!runner.game().backend().is_raw_mode());
+}
+
+#[test]
+fn draws_the_frame_around_the_game_and_log() {
+    let mut runner = runner();
+    runner.step().unwrap();
+    let backend = runner.game().backend();
+    let (cols, rows) = backend.size().unwrap();
+    let top = backend.row_text(0);
+    assert!(top.starts_with('╔') && top.ends_with('╗'));
+    assert_eq!(top.chars().filter(|c| *c == '═').count(), cols as usize - 2);
+    let bottom = backend.row_text(rows - 1);
+    assert!(bottom.starts_with('╚') && bottom.ends_with('╝'));
+
+    let separators = (1..rows - 1)
+        .map(|y| backend.row_text(y))
+        .filter(|row| row.starts_with('╠') && row.ends_with('╣'))
+        .count();
+    assert_eq!(separators, 1);
+}
+
+#[test]
+fn shows_a_notice_while_the_terminal_is_too_small() {
+    let mut runner = runner();
+    runner.step().unwrap();
+    let (cols, rows) = runner.game().backend().size().unwrap();
+
+    runner.game_mut().backend_mut().set_size(30, 4);
+    runner.script(vec![Event::Resize(30, 4)]);
+    runner.run_script().unwrap();
+    let backend = runner.game().backend();
+    assert_eq!(backend.row_text(0).trim_end(), "Terminal too small");
+    assert_eq!(
+        backend.row_text(1).trim_end(),
+        format!("Need {}x{}, have 30x4", cols, rows)
+    );
+
+    runner.game_mut().backend_mut().set_size(cols, rows);
+    runner.script(vec![Event::Resize(cols, rows)]);
+    runner.run_script().unwrap();
+    runner.step().unwrap();
+    let backend = runner.game().backend();
+    assert!(backend.row_text(0).starts_with('╔'));
+    assert!(backend.row_text(6).contains('@'));
+}
+
+#[test]
+fn pauses_while_the_terminal_is_too_small_but_still_quits() {
+    let mut runner = runner();
+    runner.step().unwrap();
+    let (cols, rows) = runner.game().backend().size().unwrap();
+
+    runner.game_mut().backend_mut().set_size(30, 4);
+    runner.script(vec![
+        Event::Resize(30, 4),
+        key(KeyCode::Right),
+        key(KeyCode::Right),
+    ]);
+    runner.run_script().unwrap();
+    runner.game_mut().backend_mut().set_size(cols, rows);
+    runner.script(vec![Event::Resize(cols, rows)]);
+    runner.run_script().unwrap();
+    runner.step().unwrap();
+    assert_eq!(col_of(&runner, 5, '@'), Some(12));
+
+    runner.game_mut().backend_mut().set_size(30, 4);
+    runner.script(vec![Event::Resize(30, 4), key(KeyCode::Esc)]);
+    assert!(!runner.run_script().unwrap());
+    assert!(runner.has_exited());
+}
+
+/// Shows the player's position in the top left corner
+#[derive(Default)]
+struct OverlayGame {}
+
+impl Game for OverlayGame {
+    fn render(&self, _gs: &GameState, ecs: &World, renderer: &mut Renderer) {
+        let players = ecs.read_storage::<Player>();
+        let positions = ecs.read_storage::<Position>();
+        if let Some((_, pos)) = (&players, &positions).join().next() {
+            let text = format!("At {},{}", pos.x, pos.y);
+            renderer.print(0, 0, &text, Color::Cyan, None);
+        }
+    }
+}
+
+#[test]
+fn games_render_on_top_of_the_map() {
+    let mut game: RogueCrossGame<OverlayGame, MemoryBackend> = Default::default();
+    game.build_map(walled_map);
+    let mut runner = TestRunner::new(game).unwrap();
+    runner.step().unwrap();
+    let text = runner.buffer_text();
+    assert!(text.lines().next().unwrap().starts_with("At 12,5#"));
+
+    runner.script(vec![key(KeyCode::Down)]);
+    runner.run_script().unwrap();
+    runner.step().unwrap();
+    assert!(runner.buffer_text().starts_with("At 12,6#"));
+}
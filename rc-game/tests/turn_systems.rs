@@ -0,0 +1,64 @@
+use crossterm::event::KeyCode;
+use rc_game::*;
+use specs::prelude::*;
+
+mod common;
+
+use common::{key, walled_map};
+
+#[derive(Default)]
+struct Counts {
+    frames: u32,
+    turns: u32,
+}
+
+struct CountFrames;
+
+impl<'a> System<'a> for CountFrames {
+    type SystemData = Write<'a, Counts>;
+
+    fn run(&mut self, mut counts: Self::SystemData) {
+        counts.frames += 1;
+    }
+}
+
+struct CountTurns;
+
+impl<'a> System<'a> for CountTurns {
+    type SystemData = (Write<'a, Counts>, ReadExpect<'a, RunState>);
+
+    fn run(&mut self, (mut counts, run_state): Self::SystemData) {
+        assert_eq!(*run_state, RunState::MonsterTurn);
+        counts.turns += 1;
+    }
+}
+
+#[derive(Default)]
+struct SystemsGame {}
+
+impl Game for SystemsGame {
+    fn register_systems(&self, dispatcher: &mut DispatcherBuilder<'static, 'static>) {
+        dispatcher.add(CountFrames, "count_frames", &[]);
+    }
+
+    fn register_turn_systems(&self, dispatcher: &mut DispatcherBuilder<'static, 'static>) {
+        dispatcher.add(CountTurns, "count_turns", &[]);
+    }
+}
+
+#[test]
+fn turn_systems_only_run_after_player_actions() {
+    let mut game: RogueCrossGame<SystemsGame, MemoryBackend> = Default::default();
+    game.build_map(walled_map);
+    let mut runner = TestRunner::new(game).unwrap();
+    runner.step_n(3).unwrap();
+    runner.script(vec![key(KeyCode::Right), key(KeyCode::Char('.'))]);
+    runner.run_script().unwrap();
+    // Not bound to an action, so no turn
+    runner.script(vec![key(KeyCode::Char('z'))]);
+    runner.run_script().unwrap();
+
+    let counts = runner.game().ecs().fetch::<Counts>();
+    assert_eq!(counts.turns, 2);
+    assert_eq!(counts.frames as u64, runner.frame());
+}